@@ -0,0 +1,449 @@
+use std::time::{Duration, Instant};
+
+use crate::minimax::SearchResult;
+use crate::two_player::TwoPlayerState;
+
+pub const INF: isize = 1_000_000_000;
+// アスピレーションウィンドウの初期の幅
+const ASPIRATION_DELTA: isize = 2;
+
+// 置換表に保存した評価値が正確な値か、上界・下界か
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    Lower, // 実際の値はvalue以上(betaカットした)
+    Upper, // 実際の値はvalue以下(alphaを超える手が無かった)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Entry {
+    pub hash: u64,
+    pub depth: usize,
+    pub value: isize,
+    pub bound: Bound,
+    pub best_action: usize,
+    pub solved: bool, // 深さ制限に届かず、部分木を終局まで読み切った値
+}
+
+// Zobristハッシュをキーにした置換表
+// 同じ場所に別の局面が来たら、より深く読んだ方を残す
+pub struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+    mask: usize,
+}
+
+impl TranspositionTable {
+    // 2^size_log2個のエントリを持つ
+    pub fn new(size_log2: usize) -> Self {
+        Self {
+            entries: vec![None; 1 << size_log2],
+            mask: (1 << size_log2) - 1,
+        }
+    }
+
+    pub fn probe(&self, hash: u64) -> Option<Entry> {
+        self.entries[hash as usize & self.mask].filter(|entry| entry.hash == hash)
+    }
+
+    pub fn store(&mut self, entry: Entry) {
+        let slot = &mut self.entries[entry.hash as usize & self.mask];
+        match slot {
+            Some(old) if old.hash != entry.hash && old.depth > entry.depth => {}
+            _ => *slot = Some(entry),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.iter_mut().for_each(|entry| *entry = None);
+    }
+}
+
+// 置換表・キラー手・ヒストリーを持ち回るアルファベータ探索
+// 手の並べ替えは 置換表の最善手 → キラー手 → ヒストリーの大きい順
+pub struct AlphaBetaSearcher {
+    pub table: TranspositionTable,
+    killers: Vec<[Option<usize>; 2]>, // 深さ(ply)ごとにbetaカットを起こした手を２つまで覚える
+    history: Vec<usize>,              // 手ごとのbetaカットした回数(深さの２乗で重み付け)
+    pub nodes: usize,
+    deadline: Option<Instant>,
+    stopped: bool,         // 制限時間を過ぎて探索を打ち切った
+    hit_depth_limit: bool, // 終局していない局面を深さ制限で評価した
+}
+
+impl AlphaBetaSearcher {
+    pub fn new(table_size_log2: usize) -> Self {
+        Self {
+            table: TranspositionTable::new(table_size_log2),
+            killers: Vec::new(),
+            history: Vec::new(),
+            nodes: 0,
+            deadline: None,
+            stopped: false,
+            hit_depth_limit: false,
+        }
+    }
+
+    // depth手先まで読み、最善の行動を返す
+    pub fn search<S: TwoPlayerState>(&mut self, state: &S, depth: usize, evaluate: &impl Fn(&S) -> isize) -> SearchResult {
+        assert!(depth > 0);
+        assert!(!state.is_done(), "cannot choose an action in a finished game");
+        self.nodes = 0;
+        self.deadline = None;
+        self.stopped = false;
+        let value = self.alpha_beta(state, depth, 0, -INF, INF, evaluate);
+        self.result(state, depth, value)
+    }
+
+    // 制限時間まで1手ずつ深く読み、最後に読み切った深さの結果を返す
+    // 深さ1は制限時間に関係なく必ず読み切る
+    pub fn iterative_deepening<S: TwoPlayerState>(
+        &mut self,
+        state: &S,
+        time_limit: Duration,
+        max_depth: usize,
+        evaluate: &impl Fn(&S) -> isize,
+    ) -> SearchResult {
+        assert!(max_depth > 0);
+        assert!(!state.is_done(), "cannot choose an action in a finished game");
+        let start = Instant::now();
+        self.nodes = 0;
+        self.deadline = None;
+        self.stopped = false;
+        self.hit_depth_limit = false;
+
+        let value = self.search_root(state, 1, -INF, INF, evaluate);
+        let mut best = self.result(state, 1, value);
+        self.deadline = Some(start + time_limit);
+        for depth in 2..=max_depth {
+            // 前の深さで終局まで読み切れていたら、それ以上深く読んでも変わらない
+            if !self.hit_depth_limit {
+                break;
+            }
+            self.hit_depth_limit = false;
+            let value = self.aspiration_search(state, depth, best.value, evaluate);
+            if self.stopped {
+                break;
+            }
+            best = self.result(state, depth, value);
+        }
+        best.nodes = self.nodes;
+        best
+    }
+
+    // 前の深さの評価値の周りの狭い窓で読み、外れたらその側の窓を広げて読み直す
+    fn aspiration_search<S: TwoPlayerState>(
+        &mut self,
+        state: &S,
+        depth: usize,
+        guess: isize,
+        evaluate: &impl Fn(&S) -> isize,
+    ) -> isize {
+        let mut delta = ASPIRATION_DELTA;
+        let mut alpha = guess - delta;
+        let mut beta = guess + delta;
+        loop {
+            let value = self.search_root(state, depth, alpha, beta, evaluate);
+            if self.stopped {
+                return value;
+            }
+            delta *= 2;
+            if value <= alpha {
+                alpha = (value - delta).max(-INF);
+            } else if value >= beta {
+                beta = (value + delta).min(INF);
+            } else {
+                return value;
+            }
+        }
+    }
+
+    // ルートでは最初の手だけ全窓で読み、残りはnull windowで最初の手を超えるかだけを調べる(PVS)
+    // 超えたときだけ全窓で読み直す
+    fn search_root<S: TwoPlayerState>(
+        &mut self,
+        state: &S,
+        depth: usize,
+        mut alpha: isize,
+        beta: isize,
+        evaluate: &impl Fn(&S) -> isize,
+    ) -> isize {
+        self.nodes += 1;
+        let original_alpha = alpha;
+        let table_action = self.table.probe(state.hash()).map(|entry| entry.best_action);
+        let actions = self.order_actions(state.legal_actions(), table_action, 0);
+        let mut best_value = -INF;
+        let mut best_action = actions[0];
+        for (i, action) in actions.into_iter().enumerate() {
+            let mut next_state = state.clone();
+            next_state.advance(action);
+            let value = if i == 0 {
+                -self.alpha_beta(&next_state, depth - 1, 1, -beta, -alpha, evaluate)
+            } else {
+                let value = -self.alpha_beta(&next_state, depth - 1, 1, -alpha - 1, -alpha, evaluate);
+                if value > alpha && value < beta && !self.stopped {
+                    -self.alpha_beta(&next_state, depth - 1, 1, -beta, -alpha, evaluate)
+                } else {
+                    value
+                }
+            };
+            if self.stopped {
+                return 0;
+            }
+            if value > best_value {
+                best_value = value;
+                best_action = action;
+            }
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+        self.store(state.hash(), depth, best_value, original_alpha, beta, best_action);
+        best_value
+    }
+
+    // 今までにhit_depth_limitが立っていなければ、読み切った値として保存する
+    fn store(&mut self, hash: u64, depth: usize, value: isize, alpha: isize, beta: isize, best_action: usize) {
+        let bound = if value <= alpha {
+            Bound::Upper
+        } else if value >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.store(Entry {
+            hash,
+            depth,
+            value,
+            bound,
+            best_action,
+            solved: !self.hit_depth_limit,
+        });
+    }
+
+    // 64ノードごとに制限時間を確かめる
+    fn check_time(&mut self) {
+        if self.nodes & 63 == 0 {
+            if let Some(deadline) = self.deadline {
+                if Instant::now() >= deadline {
+                    self.stopped = true;
+                }
+            }
+        }
+    }
+
+    // 置換表の最善手をたどって読み筋を作る
+    fn result<S: TwoPlayerState>(&self, state: &S, depth: usize, value: isize) -> SearchResult {
+        let mut principal_variation = Vec::new();
+        let mut now_state = state.clone();
+        while principal_variation.len() < depth && !now_state.is_done() {
+            let Some(entry) = self.table.probe(now_state.hash()) else {
+                break;
+            };
+            if !now_state.legal_actions().contains(&entry.best_action) {
+                break;
+            }
+            principal_variation.push(entry.best_action);
+            now_state.advance(entry.best_action);
+        }
+        if principal_variation.is_empty() {
+            principal_variation.push(state.legal_actions()[0]);
+        }
+        SearchResult {
+            action: principal_variation[0],
+            value,
+            principal_variation,
+            nodes: self.nodes,
+            depth,
+        }
+    }
+
+    fn order_actions(&self, mut actions: Vec<usize>, table_action: Option<usize>, ply: usize) -> Vec<usize> {
+        let killers = self.killers.get(ply).copied().unwrap_or([None, None]);
+        let priority = |action: usize| {
+            if Some(action) == table_action {
+                (2, 0)
+            } else if killers.contains(&Some(action)) {
+                (1, 0)
+            } else {
+                (0, self.history.get(action).copied().unwrap_or(0))
+            }
+        };
+        actions.sort_by_key(|&action| std::cmp::Reverse(priority(action)));
+        actions
+    }
+
+    fn record_cutoff(&mut self, action: usize, depth: usize, ply: usize) {
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None, None]);
+        }
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(action) {
+            killers[1] = killers[0];
+            killers[0] = Some(action);
+        }
+        if self.history.len() <= action {
+            self.history.resize(action + 1, 0);
+        }
+        self.history[action] += depth * depth;
+    }
+
+    // 手番のプレイヤーから見た評価を返す(ネガマックス形式)
+    // 制限時間を過ぎたらstoppedを立てて0を返す。そのときの値は使わないこと
+    // hit_depth_limitはこの部分木の分だけを調べてから呼び出し元の値に足す
+    fn alpha_beta<S: TwoPlayerState>(
+        &mut self,
+        state: &S,
+        depth: usize,
+        ply: usize,
+        alpha: isize,
+        beta: isize,
+        evaluate: &impl Fn(&S) -> isize,
+    ) -> isize {
+        let outer_hit_depth_limit = std::mem::take(&mut self.hit_depth_limit);
+        let value = self.alpha_beta_node(state, depth, ply, alpha, beta, evaluate);
+        self.hit_depth_limit |= outer_hit_depth_limit;
+        value
+    }
+
+    fn alpha_beta_node<S: TwoPlayerState>(
+        &mut self,
+        state: &S,
+        depth: usize,
+        ply: usize,
+        mut alpha: isize,
+        mut beta: isize,
+        evaluate: &impl Fn(&S) -> isize,
+    ) -> isize {
+        self.nodes += 1;
+        self.check_time();
+        if self.stopped {
+            return 0;
+        }
+        if state.is_done() || depth == 0 {
+            if !state.is_done() {
+                self.hit_depth_limit = true;
+            }
+            return evaluate(state);
+        }
+
+        let hash = state.hash();
+        let original_alpha = alpha;
+        let mut table_action = None;
+        if let Some(entry) = self.table.probe(hash) {
+            table_action = Some(entry.best_action);
+            if entry.depth >= depth {
+                // 読み切っていない値を使うと、この局面も深さ制限で評価したことになる
+                if !entry.solved {
+                    self.hit_depth_limit = true;
+                }
+                match entry.bound {
+                    Bound::Exact => return entry.value,
+                    Bound::Lower => alpha = alpha.max(entry.value),
+                    Bound::Upper => beta = beta.min(entry.value),
+                }
+                if alpha >= beta {
+                    return entry.value;
+                }
+            }
+        }
+
+        let actions = self.order_actions(state.legal_actions(), table_action, ply);
+        let mut best_value = -INF;
+        let mut best_action = actions[0];
+        for action in actions {
+            let mut next_state = state.clone();
+            next_state.advance(action);
+            let value = -self.alpha_beta(&next_state, depth - 1, ply + 1, -beta, -alpha, evaluate);
+            if self.stopped {
+                return 0;
+            }
+            if value > best_value {
+                best_value = value;
+                best_action = action;
+            }
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                self.record_cutoff(action, depth, ply);
+                break;
+            }
+        }
+
+        self.store(hash, depth, best_value, original_alpha, beta, best_action);
+        best_value
+    }
+}
+
+// 新しい置換表でdepth手先までアルファベータ法で読む
+pub fn alpha_beta_action<S: TwoPlayerState>(state: &S, depth: usize, evaluate: &impl Fn(&S) -> isize) -> SearchResult {
+    AlphaBetaSearcher::new(16).search(state, depth, evaluate)
+}
+
+// 新しい置換表で制限時間まで反復深化アルファベータ法で読む
+pub fn iterative_deepening_action<S: TwoPlayerState>(
+    state: &S,
+    time_limit: Duration,
+    evaluate: &impl Fn(&S) -> isize,
+) -> SearchResult {
+    AlphaBetaSearcher::new(16).iterative_deepening(state, time_limit, usize::MAX, evaluate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alternate_maze::{AlternateMazeState, END_TURN};
+    use crate::minimax::{minimax_action, score_evaluation};
+
+    const LONG: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn matches_minimax() {
+        for seed in 0..5 {
+            let state = AlternateMazeState::new(seed);
+            for depth in 1..=5 {
+                let expected = minimax_action(&state, depth, &score_evaluation).value;
+                assert_eq!(alpha_beta_action(&state, depth, &score_evaluation).value, expected, "seed {} depth {}", seed, depth);
+            }
+        }
+    }
+
+    // 制限時間で打ち切った深さの値が結果に混ざらない
+    #[test]
+    fn interrupted_depth_does_not_leak() {
+        for seed in 0..5 {
+            let state = AlternateMazeState::new(seed);
+            for time_limit_us in [10, 30, 100, 300, 1000] {
+                let result = iterative_deepening_action(&state, Duration::from_micros(time_limit_us), &score_evaluation);
+                let expected = alpha_beta_action(&state, result.depth, &score_evaluation).value;
+                assert_eq!(result.value, expected, "seed {} time limit {}us", seed, time_limit_us);
+            }
+        }
+    }
+
+    #[test]
+    fn reads_to_the_end_of_the_game() {
+        let state = AlternateMazeState::new(0);
+        let result = AlphaBetaSearcher::new(16).iterative_deepening(&state, LONG, usize::MAX, &score_evaluation);
+        assert_eq!(result.depth, END_TURN);
+        assert_eq!(result.value, alpha_beta_action(&state, END_TURN, &score_evaluation).value);
+    }
+
+    // 前の手の探索で残った深い置換表のエントリで、読み切ったと勘違いしない
+    #[test]
+    fn reused_searcher_matches_a_fresh_one() {
+        for seed in 0..20 {
+            let mut state = AlternateMazeState::new(seed);
+            let mut searcher = AlphaBetaSearcher::new(16);
+            while !state.is_done() {
+                let result = searcher.iterative_deepening(&state, LONG, 4, &score_evaluation);
+                let fresh = AlphaBetaSearcher::new(16).iterative_deepening(&state, LONG, 4, &score_evaluation);
+                assert_eq!(result.value, fresh.value, "seed {} turn {}", seed, state.turn);
+                // 終局まで読める局面なら、置換表の読み切った値で浅く止まってよい
+                if fresh.depth < END_TURN - state.turn {
+                    assert_eq!(result.depth, fresh.depth, "seed {} turn {}", seed, state.turn);
+                }
+                state.advance(result.action);
+            }
+        }
+    }
+}
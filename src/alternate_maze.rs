@@ -0,0 +1,249 @@
+use rand::prelude::*;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::coord::{Coord, DX, DY};
+use crate::two_player::{TwoPlayerState, WinningStatus};
+
+pub const H: usize = 5;
+pub const W: usize = 5;
+pub const END_TURN: usize = 10;
+const MAX_SCORE: usize = 9 * END_TURN;
+
+// Zobristハッシュ用の乱数表
+// プレイヤーはスロットではなく先手(0)/後手(1)で区別する
+struct ZobristTable {
+    points: [[[u64; 10]; W]; H],
+    characters: [[[u64; W]; H]; 2],
+    scores: [[u64; MAX_SCORE + 1]; 2],
+    turns: [u64; END_TURN + 1],
+}
+
+fn zobrist() -> &'static ZobristTable {
+    static TABLE: OnceLock<ZobristTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut rng = StdRng::seed_from_u64(0x5EED);
+        let mut table = ZobristTable {
+            points: [[[0; 10]; W]; H],
+            characters: [[[0; W]; H]; 2],
+            scores: [[0; MAX_SCORE + 1]; 2],
+            turns: [0; END_TURN + 1],
+        };
+        table.points.iter_mut().flatten().flatten().for_each(|key| *key = rng.gen());
+        table.characters.iter_mut().flatten().flatten().for_each(|key| *key = rng.gen());
+        table.scores.iter_mut().flatten().for_each(|key| *key = rng.gen());
+        table.turns.iter_mut().for_each(|key| *key = rng.gen());
+        table
+    })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Character {
+    pub coord: Coord,
+    pub game_score: usize,
+}
+
+impl Character {
+    fn new(y: isize, x: isize) -> Self {
+        Self {
+            coord: Coord::new(y, x),
+            game_score: 0,
+        }
+    }
+}
+
+// 二人交互ゲームの例
+// 二人のプレイヤーが交互に上下左右四方向のいずれかに1マスずつ進む。
+// 床にあるポイントを踏むと自身のスコアとなり、床のポイントが消える。
+// END_TURNの時点で相手よりスコアが高ければ勝ち
+#[derive(Clone, Copy, Debug)]
+pub struct AlternateMazeState {
+    pub points: [[usize; W]; H], // 床のポイントを1~9で表現する
+    pub turn: usize,             // 現在のターン
+    pub characters: [Character; 2], // characters[0]が常に手番のプレイヤー
+    pub hash: u64,                  // 盤面全体のZobristハッシュ。advanceで差分更新する
+}
+
+impl AlternateMazeState {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let characters = [
+            Character::new((H / 2) as isize, (W / 2) as isize - 1),
+            Character::new((H / 2) as isize, (W / 2) as isize + 1),
+        ];
+        let mut points = [[0; W]; H];
+        for (y, row) in points.iter_mut().enumerate() {
+            for (x, point) in row.iter_mut().enumerate() {
+                let is_character = characters
+                    .iter()
+                    .any(|character| character.coord.y == y as isize && character.coord.x == x as isize);
+                if !is_character {
+                    *point = rng.gen_range(0..10);
+                }
+            }
+        }
+        let mut state = Self {
+            points,
+            turn: 0,
+            characters,
+            hash: 0,
+        };
+        state.hash = state.compute_hash();
+        state
+    }
+
+    // 盤面全体からZobristハッシュを計算する
+    fn compute_hash(&self) -> u64 {
+        let table = zobrist();
+        let mut hash = table.turns[self.turn];
+        for (y, row) in self.points.iter().enumerate() {
+            for (x, point) in row.iter().enumerate() {
+                hash ^= table.points[y][x][*point];
+            }
+        }
+        let first = if self.is_first_player() { 0 } else { 1 };
+        for (slot, character) in self.characters.iter().enumerate() {
+            let player = slot ^ first;
+            hash ^= table.characters[player][character.coord.y as usize][character.coord.x as usize];
+            hash ^= table.scores[player][character.game_score];
+        }
+        hash
+    }
+
+    // ゲームの終了判定
+    pub fn is_done(&self) -> bool {
+        self.turn == END_TURN
+    }
+
+    // 手番のプレイヤーが指定したactionでゲームを1ターン進め、手番を交代する
+    pub fn advance(&mut self, action: usize) {
+        let table = zobrist();
+        let player = if self.is_first_player() { 0 } else { 1 };
+        let character = &mut self.characters[0];
+        let (y, x) = (character.coord.y as usize, character.coord.x as usize);
+        self.hash ^= table.characters[player][y][x];
+        character.coord.x += DX[action];
+        character.coord.y += DY[action];
+        let (y, x) = (character.coord.y as usize, character.coord.x as usize);
+        self.hash ^= table.characters[player][y][x];
+        let point = &mut self.points[y][x];
+        if *point > 0 {
+            self.hash ^= table.scores[player][character.game_score] ^ table.points[y][x][*point] ^ table.points[y][x][0];
+            character.game_score += *point;
+            self.hash ^= table.scores[player][character.game_score];
+            *point = 0;
+        }
+        self.hash ^= table.turns[self.turn] ^ table.turns[self.turn + 1];
+        self.turn += 1;
+        self.characters.swap(0, 1);
+        debug_assert_eq!(self.hash, self.compute_hash());
+    }
+
+    // 手番のプレイヤーが可能な行動を全て取得する
+    pub fn legal_actions(&self) -> Vec<usize> {
+        let character = &self.characters[0];
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let ty = character.coord.y + DY[action];
+            let tx = character.coord.x + DX[action];
+            if ty >= 0 && ty < H as isize && tx >= 0 && tx < W as isize {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    // 手番のプレイヤーから見た勝敗
+    pub fn winning_status(&self) -> WinningStatus {
+        if !self.is_done() {
+            return WinningStatus::Playing;
+        }
+        let (me, opponent) = (self.characters[0].game_score, self.characters[1].game_score);
+        if me > opponent {
+            WinningStatus::Win
+        } else if me < opponent {
+            WinningStatus::Lose
+        } else {
+            WinningStatus::Draw
+        }
+    }
+
+    // 手番のプレイヤーから見た評価(自分のスコア - 相手のスコア)
+    pub fn get_score(&self) -> isize {
+        self.characters[0].game_score as isize - self.characters[1].game_score as isize
+    }
+
+    // 先手(A)から見た勝敗
+    pub fn first_player_status(&self) -> WinningStatus {
+        let status = self.winning_status();
+        if self.is_first_player() {
+            return status;
+        }
+        match status {
+            WinningStatus::Win => WinningStatus::Lose,
+            WinningStatus::Lose => WinningStatus::Win,
+            status => status,
+        }
+    }
+
+    // 先手(A)が手番ならtrue
+    pub fn is_first_player(&self) -> bool {
+        self.turn.is_multiple_of(2)
+    }
+}
+
+impl TwoPlayerState for AlternateMazeState {
+    fn legal_actions(&self) -> Vec<usize> {
+        AlternateMazeState::legal_actions(self)
+    }
+
+    fn advance(&mut self, action: usize) {
+        AlternateMazeState::advance(self, action)
+    }
+
+    fn is_done(&self) -> bool {
+        AlternateMazeState::is_done(self)
+    }
+
+    fn winning_status(&self) -> WinningStatus {
+        AlternateMazeState::winning_status(self)
+    }
+
+    fn get_score(&self) -> isize {
+        AlternateMazeState::get_score(self)
+    }
+
+    fn hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+// 現在のゲーム状況を文字列にする。先手をA、後手をBで表す
+impl fmt::Display for AlternateMazeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, b) = if self.is_first_player() {
+            (&self.characters[0], &self.characters[1])
+        } else {
+            (&self.characters[1], &self.characters[0])
+        };
+        writeln!(f, "turn:\t{}", self.turn)?;
+        writeln!(f, "score(A):\t{}\ty: {} x: {}", a.game_score, a.coord.y, a.coord.x)?;
+        writeln!(f, "score(B):\t{}\ty: {} x: {}", b.game_score, b.coord.y, b.coord.x)?;
+        for h in 0..H {
+            for w in 0..W {
+                let is_here = |character: &Character| character.coord.y == h as isize && character.coord.x == w as isize;
+                if is_here(a) {
+                    write!(f, "A")?;
+                } else if is_here(b) {
+                    write!(f, "B")?;
+                } else if self.points[h][w] > 0 {
+                    write!(f, "{}", self.points[h][w])?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
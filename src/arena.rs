@@ -0,0 +1,151 @@
+use rand::prelude::*;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::alternate_maze::AlternateMazeState;
+use crate::two_player::WinningStatus;
+
+type State = AlternateMazeState;
+pub type AIFunction = Box<dyn Fn(&State, &mut StdRng) -> usize>;
+
+pub struct StringAIPair {
+    pub name: String,
+    pub function: AIFunction,
+}
+
+impl StringAIPair {
+    pub fn new(name: impl Into<String>, function: impl Fn(&State, &mut StdRng) -> usize + 'static) -> Self {
+        Self {
+            name: name.into(),
+            function: Box::new(function),
+        }
+    }
+}
+
+pub fn random_action(state: &State, rng: &mut StdRng) -> usize {
+    let legal_actions = state.legal_actions();
+    legal_actions[rng.gen_range(0..legal_actions.len())]
+}
+
+// 1局の結果。times[i], moves[i]はais[i]が使った思考時間と手数
+pub struct GameRecord {
+    pub first_player_status: WinningStatus,
+    pub times: [Duration; 2],
+    pub moves: [usize; 2],
+}
+
+// ais[0]を先手、ais[1]を後手にしてseedの盤面で1局打つ
+pub fn play_game(ais: [&StringAIPair; 2], seed: u64) -> GameRecord {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut state = State::new(seed);
+    let mut times = [Duration::ZERO; 2];
+    let mut moves = [0; 2];
+    for player in [0, 1].into_iter().cycle() {
+        if state.is_done() {
+            break;
+        }
+        let start = Instant::now();
+        let action = (ais[player].function)(&state, &mut rng);
+        times[player] += start.elapsed();
+        moves[player] += 1;
+        state.advance(action);
+    }
+    GameRecord {
+        first_player_status: state.first_player_status(),
+        times,
+        moves,
+    }
+}
+
+// あるAIから見た対戦成績
+#[derive(Clone, Debug)]
+pub struct MatchResult {
+    pub name: String,
+    pub opponent: String,
+    pub wins: usize,
+    pub losses: usize,
+    pub draws: usize,
+    pub times: [Duration; 2], // [自分, 相手]の思考時間の合計
+    pub moves: [usize; 2],    // [自分, 相手]の手数の合計
+}
+
+impl MatchResult {
+    pub fn games(&self) -> usize {
+        self.wins + self.losses + self.draws
+    }
+
+    // 引き分けを0.5勝として数えた勝率。1局も打っていなければ0.5
+    pub fn win_rate(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + 0.5 * self.draws as f64) / self.games() as f64
+    }
+
+    // 勝率のWilsonスコア信頼区間。zは1.96なら95%
+    // 全勝や全敗でも幅が0にならない。1局も打っていなければ(0, 1)
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        if self.games() == 0 {
+            return (0.0, 1.0);
+        }
+        let n = self.games() as f64;
+        let p = self.win_rate();
+        let z2 = z * z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let margin = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        ((center - margin).max(0.0), (center + margin).min(1.0))
+    }
+
+    // 1手あたりの平均思考時間。0が自分、1が相手
+    pub fn average_time_per_move(&self, player: usize) -> Duration {
+        if self.moves[player] == 0 {
+            return Duration::ZERO;
+        }
+        self.times[player] / self.moves[player] as u32
+    }
+}
+
+impl fmt::Display for MatchResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (low, high) = self.confidence_interval(1.96);
+        writeln!(f, "{} vs {}", self.name, self.opponent)?;
+        writeln!(f, "win/lose/draw:\t{}/{}/{}", self.wins, self.losses, self.draws)?;
+        writeln!(f, "win rate:\t{:.3} (95% CI {:.3} - {:.3})", self.win_rate(), low, high)?;
+        write!(
+            f,
+            "time per move:\t{:?} / {:?}",
+            self.average_time_per_move(0),
+            self.average_time_per_move(1)
+        )
+    }
+}
+
+// seed_number個の盤面それぞれで先後を入れ替えて2局ずつ打ち、aから見た成績を返す
+pub fn play_match(a: &StringAIPair, b: &StringAIPair, seed_number: usize) -> MatchResult {
+    let mut result = MatchResult {
+        name: a.name.clone(),
+        opponent: b.name.clone(),
+        wins: 0,
+        losses: 0,
+        draws: 0,
+        times: [Duration::ZERO; 2],
+        moves: [0; 2],
+    };
+    for seed in 0..seed_number as u64 {
+        for a_seat in [0, 1] {
+            let ais = if a_seat == 0 { [a, b] } else { [b, a] };
+            let record = play_game(ais, seed);
+            match (record.first_player_status, a_seat) {
+                (WinningStatus::Win, 0) | (WinningStatus::Lose, 1) => result.wins += 1,
+                (WinningStatus::Lose, 0) | (WinningStatus::Win, 1) => result.losses += 1,
+                _ => result.draws += 1,
+            }
+            for player in [0, 1] {
+                let seat = player ^ a_seat;
+                result.times[player] += record.times[seat];
+                result.moves[player] += record.moves[seat];
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,175 @@
+use rand::prelude::*;
+use std::cmp::Ordering;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::coord::{Coord, DX, DY};
+use crate::move_policy::{Greedy, MovePolicy};
+
+pub const H: usize = 30;
+pub const W: usize = 30;
+pub const END_TURN: usize = 100;
+
+// 一人ゲームの例
+// 1ターンに上下左右四方向のいずれかに1マスずつ進む。
+// 床にあるポイントを踏むと自身のスコアとなり、床のポイントが消える。
+// END_TURNの時点のスコアを高くすることが目的
+#[derive(Clone)]
+pub struct AutoMoveMazeState {
+    pub points: [[usize; W]; H], // 床のポイントを1~9で表現する
+    pub turn: usize,             // 現在のターン
+    pub characters: Vec<Coord>,
+    pub game_score: usize, // ゲーム上で実際に得たスコア
+    pub evaluated_score: usize,
+    pub policy: Rc<dyn MovePolicy>, // キャラクターの動き方
+}
+
+impl Ord for AutoMoveMazeState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.evaluated_score.cmp(&other.evaluated_score)
+    }
+}
+
+impl PartialOrd for AutoMoveMazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for AutoMoveMazeState {}
+
+impl PartialEq for AutoMoveMazeState {
+    fn eq(&self, other: &Self) -> bool {
+        self.evaluated_score == other.evaluated_score
+    }
+}
+
+impl AutoMoveMazeState {
+    pub fn new(seed: u8, character_n: usize) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([seed; 32]);
+        let character = Coord::new(rng.gen_range(0..H) as isize, rng.gen_range(0..W) as isize);
+        let mut points = [[0; W]; H];
+        for (y, row) in points.iter_mut().enumerate() {
+            for (x, point) in row.iter_mut().enumerate() {
+                if y as isize == character.y && x as isize == character.x {
+                    continue;
+                }
+                *point = rng.gen_range(0..10) as usize;
+            }
+        }
+        Self {
+            points,
+            turn: 0,
+            characters: vec![Coord {x: 0, y: 0}; character_n],
+            game_score: 0,
+            evaluated_score: 0,
+            policy: Rc::new(Greedy::default()),
+        }
+    }
+
+    // キャラクターの動き方を差し替える
+    pub fn with_policy(mut self, policy: impl MovePolicy + 'static) -> Self {
+        self.policy = Rc::new(policy);
+        self
+    }
+
+    fn move_player(&mut self, character_id: usize) {
+        let action = self.policy.next_action(self, character_id);
+        let character = &mut self.characters[character_id];
+        character.y += DY[action];
+        character.x += DX[action];
+    }
+
+    pub fn set_character(&mut self, character_id: usize, y: usize, x: usize) {
+        self.characters[character_id].y = y as isize;
+        self.characters[character_id].x = x as isize;
+    }
+
+    // ゲームの終了判定
+    pub fn is_done(&self) -> bool {
+        self.turn == END_TURN
+    }
+
+    // 指定したactionでゲームを1ターン進める
+    pub fn advance(&mut self) {
+        for character_id in 0..self.characters.len() {
+            self.move_player(character_id);
+        }
+        for character in &mut self.characters {
+            let point = &mut self.points[character.y as usize][character.x as usize];
+            self.game_score += *point;
+            *point = 0;
+        }
+        self.turn += 1;
+    }
+
+    pub fn get_score(&self, is_print: bool) -> usize {
+        self.simulate(is_print).game_score
+    }
+
+    // 配置から最後まで進めた後、誰にも踏まれずに残ったポイントを返す
+    pub fn remaining_points(&self) -> [[usize; W]; H] {
+        self.simulate(false).points
+    }
+
+    fn simulate(&self, is_print: bool) -> Self {
+        let mut tmp_state = self.clone();
+        for character in &mut tmp_state.characters {
+            let point = &mut tmp_state.points[character.y as usize][character.x as usize];
+            *point = 0;
+        }
+        while !tmp_state.is_done() {
+            tmp_state.advance();
+            if is_print {
+                println!("{}", tmp_state);
+            }
+        }
+        tmp_state
+    }
+
+    // ランダムに１匹を遷移させる
+    pub fn transition(&mut self, rng: &mut impl Rng) {
+        let character_id = rng.gen_range(0..self.characters.len());
+        let character = &mut self.characters[character_id];
+        character.x = rng.gen_range(0..100000) % W as isize;
+        character.y = rng.gen_range(0..100000) % H as isize;
+    }
+
+    pub fn init(&mut self, rng: &mut impl Rng) {
+        for character in &mut self.characters {
+            character.y = rng.gen_range(0..100000) % H as isize;
+            character.x = rng.gen_range(0..100000) % W as isize;
+        }
+    }
+}
+
+impl fmt::Display for AutoMoveMazeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut output = String::new();
+        output.push_str(&format!("turn:\t{}\n", self.turn));
+
+        output.push_str(&format!("score:\t{}\n", self.game_score));
+        let mut board_chars = vec![vec!['.'; W]; H];
+        for h in 0..H {
+            for w in 0..W {
+                let mut is_written = false;
+                for character in &self.characters {
+                    if character.y == h as isize && character.x == w as isize {
+                        is_written = true;
+                        break;
+                    }
+                    board_chars[character.y as usize][character.x as usize] = '@';
+                }
+                if !is_written {
+                    if self.points[h][w] > 0 {
+                        output.push_str(&self.points[h][w].to_string());
+                    } else {
+                        output.push('.');
+                    }
+                }
+            }
+            output.push('\n');
+        }
+        f.write_str(&output)
+    }
+}
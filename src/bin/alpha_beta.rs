@@ -0,0 +1,35 @@
+use std::time::Instant;
+
+use search_algorithm::alpha_beta::AlphaBetaSearcher;
+use search_algorithm::alternate_maze::{AlternateMazeState, END_TURN};
+use search_algorithm::minimax::{minimax_action, score_evaluation};
+
+// 同じ局面を深さを変えながら読み、ミニマックス法とアルファベータ法の探索ノード数と時間を比べる
+fn main() {
+    let state = AlternateMazeState::new(0);
+    println!("{}", state);
+    println!("depth\tminimax nodes\ttime\talphabeta nodes\ttime\tvalue\tpv");
+    for depth in 1..=END_TURN {
+        let minimax_column = if depth <= 8 {
+            let start = Instant::now();
+            let result = minimax_action(&state, depth, &score_evaluation);
+            let elapsed = start.elapsed();
+            format!("{}\t{:?}", result.nodes, elapsed)
+        } else {
+            "-\t-".to_string()
+        };
+
+        let mut searcher = AlphaBetaSearcher::new(16);
+        let start = Instant::now();
+        let result = searcher.search(&state, depth, &score_evaluation);
+        println!(
+            "{}\t{}\t{}\t{:?}\t{}\t{:?}",
+            depth,
+            minimax_column,
+            result.nodes,
+            start.elapsed(),
+            result.value,
+            result.principal_variation
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use rand::prelude::*;
+use search_algorithm::alternate_maze::AlternateMazeState;
+
+fn random_action(state: &AlternateMazeState, rng: &mut impl Rng) -> usize {
+    let legal_actions = state.legal_actions();
+    legal_actions[rng.gen_range(0..legal_actions.len())]
+}
+
+fn play_game(seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut state = AlternateMazeState::new(seed);
+    println!("{}", state);
+    while !state.is_done() {
+        // 1p
+        println!("1p ------------------------------------");
+        let action = random_action(&state, &mut rng);
+        println!("action {}", action);
+        state.advance(action);
+        println!("{}", state);
+        if state.is_done() {
+            break;
+        }
+
+        // 2p
+        println!("2p ------------------------------------");
+        let action = random_action(&state, &mut rng);
+        println!("action {}", action);
+        state.advance(action);
+        println!("{}", state);
+    }
+
+    println!("1p: {:?}", state.first_player_status());
+}
+
+fn main() {
+    play_game(0);
+}
@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use search_algorithm::alpha_beta::iterative_deepening_action;
+use search_algorithm::arena::{play_match, random_action, StringAIPair};
+use search_algorithm::budget::Budget;
+use search_algorithm::mcts::{mcts_action, MctsConfig};
+use search_algorithm::minimax::{minimax_action, score_evaluation};
+
+// 名前付きのAI同士を先後入れ替えて対戦させ、成績を表示する
+fn main() {
+    let ais = [
+        StringAIPair::new("randomAction", random_action),
+        StringAIPair::new("minimaxAction(3)", |state, _| minimax_action(state, 3, &score_evaluation).action),
+        StringAIPair::new("iterativeDeepening(1ms)", |state, _| {
+            iterative_deepening_action(state, Duration::from_millis(1), &score_evaluation).action
+        }),
+        StringAIPair::new("mctsAction(1ms)", |state, rng| {
+            mcts_action(state, MctsConfig::default(), &Budget::time(Duration::from_millis(1)), rng)
+        }),
+    ];
+    for (i, a) in ais.iter().enumerate() {
+        for b in &ais[i + 1..] {
+            println!("{}\n", play_match(a, b, 50));
+        }
+    }
+}
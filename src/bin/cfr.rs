@@ -0,0 +1,47 @@
+use rand::prelude::*;
+use search_algorithm::budget::Budget;
+use search_algorithm::cfr::CfrSolver;
+use search_algorithm::duct::Duct;
+use search_algorithm::mcts::MctsConfig;
+use search_algorithm::simultaneous_maze::SimultaneousMazeState;
+
+// 小さな盤面でCFRの平均戦略のexploitabilityが0に近づく様子と、
+// 同じ盤面でDUCTの訪問回数の分布を戦略とみなしたときのexploitabilityを表示する
+fn main() {
+    let state = SimultaneousMazeState::with_size(0, 3, 3, 3);
+    println!("{}", state);
+    let mut solver = CfrSolver::new(&state);
+    println!("tree size: {}", solver.tree_size());
+
+    let uniform = solver.policy_exploitability(|state, player| {
+        let n = state.legal_actions(player).len();
+        vec![1.0 / n as f64; n]
+    });
+    println!("uniform random exploitability: {:.4}", uniform);
+
+    println!("iterations\texploitability");
+    let mut iterations = 1;
+    while iterations <= 10000 {
+        solver.run(iterations - solver.iterations);
+        println!("{}\t{:.4}", solver.iterations, solver.exploitability());
+        iterations *= 10;
+    }
+    for player in [0, 1] {
+        println!("root strategy of player {}: {:?}", player, solver.root_strategy(player));
+    }
+
+    println!("duct iterations\texploitability");
+    for number in [100, 1000, 10000] {
+        let mut rng = StdRng::seed_from_u64(0);
+        let budget = Budget::iterations(number);
+        // DUCTは同時手番の局面ごとに探索し直し、二人の訪問回数の割合を戦略とする
+        let exploitability = solver.policy_exploitability(|state, player| {
+            let mut duct = Duct::new(state, MctsConfig::default());
+            duct.search(&budget, &mut rng);
+            let stats = duct.root_stats(player);
+            let total: usize = stats.iter().map(|stats| stats.visits).sum();
+            stats.iter().map(|stats| stats.visits as f64 / total as f64).collect()
+        });
+        println!("{}\t{:.4}", number, exploitability);
+    }
+}
@@ -0,0 +1,29 @@
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+use search_algorithm::contest::{format_actions, solve};
+use search_algorithm::evaluator::NearbyPotential;
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::beam_search_action;
+
+// コンテスト用の提出形式
+// 標準入力からinstance.rsの形式の盤面を読み、行動をR, L, D, Uの1行で標準出力に書く
+// 読めない盤面なら理由を標準エラーに書いて失敗する
+fn main() -> ExitCode {
+    let mut input = String::new();
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("failed to read stdin: {}", error);
+        return ExitCode::FAILURE;
+    }
+    let state = match MazeState::parse(&input) {
+        Ok(state) => state,
+        Err(error) => {
+            eprintln!("invalid instance: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let evaluator = NearbyPotential::new(&state, 1.0);
+    let actions = solve(state, |state| beam_search_action(state, 20, 3, &evaluator));
+    println!("{}", format_actions(&actions));
+    ExitCode::SUCCESS
+}
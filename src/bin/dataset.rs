@@ -0,0 +1,31 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use search_algorithm::dataset::{record_game, write_jsonl};
+use search_algorithm::evaluator::NearbyPotential;
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::beam_search_action;
+
+// 幅の広いビームサーチで各seedの盤面を最後まで打ち、全ての局面をJSON Linesで書き出す
+// 形式はdataset.rsを参照。引数: [出力ファイル(無ければ標準出力)] [seedの数(既定100)]
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let mut writer: Box<dyn Write> = match args.next() {
+        Some(path) if path != "-" => Box::new(BufWriter::new(File::create(path)?)),
+        _ => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+    let seed_number: usize = args.next().map_or(100, |number| number.parse().expect("seed number"));
+    assert!(seed_number <= 256);
+    let mut total = 0;
+    for seed in 0..seed_number {
+        let seed = seed as u8;
+        let state = MazeState::new(seed);
+        let evaluator = NearbyPotential::new(&state, 1.0);
+        let records = record_game(seed, state, |state| beam_search_action(state, 20, 3, &evaluator));
+        total += records.last().map_or(0, |record| record.final_score);
+        write_jsonl(&records, &mut writer)?;
+    }
+    writer.flush()?;
+    eprintln!("seeds: {} average score: {}", seed_number, total / seed_number.max(1));
+    Ok(())
+}
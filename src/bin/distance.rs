@@ -0,0 +1,41 @@
+use search_algorithm::coord::Coord;
+use search_algorithm::distance::DistanceMap;
+use search_algorithm::evaluator::{NearbyPotential, ScoreOnly};
+use search_algorithm::maze::{MazeState, H, W};
+use search_algorithm::maze_search::beam_search_action;
+
+fn play_game(mut state: MazeState, action: impl Fn(&MazeState) -> usize) -> usize {
+    while !state.is_done() {
+        state.advance(action(&state));
+    }
+    state.game_score
+}
+
+// 壁のある迷路で、game_scoreだけの評価と距離を考えた評価のビームサーチを比べる
+fn main() {
+    let state = MazeState::with_walls(0);
+    println!("{}", state);
+    let map = DistanceMap::new(&state, state.character);
+    let reachable = (0..H * W)
+        .filter(|i| map.get(Coord::new((i / W) as isize, (i % W) as isize)).is_some())
+        .count();
+    println!("reachable cells from @: {}", reachable);
+
+    let game_number = 20;
+    println!("seed\tscore\tdistance");
+    let (mut score_sum, mut distance_sum) = (0, 0);
+    for seed in 0..game_number {
+        let state = MazeState::with_walls(seed);
+        let score = play_game(state, |state| beam_search_action(state, 5, 2, &ScoreOnly));
+        let evaluator = NearbyPotential::new(&state, 1.0);
+        let distance = play_game(state, |state| beam_search_action(state, 5, 2, &evaluator));
+        println!("{}\t{}\t{}", seed, score, distance);
+        score_sum += score;
+        distance_sum += distance;
+    }
+    println!(
+        "average\t{}\t{}",
+        score_sum / game_number as usize,
+        distance_sum / game_number as usize
+    );
+}
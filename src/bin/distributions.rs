@@ -0,0 +1,59 @@
+use search_algorithm::evaluator::{Evaluator, NearbyPotential, ScoreOnly};
+use search_algorithm::generator::{standard_distributions, InstanceConfig};
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::{beam_search_action, chokudai_search_action, greedy_action};
+
+// 評価関数は盤面ごとに1回だけ作る(距離マップのキャッシュを持つため)
+type EvaluatorFactory = Box<dyn Fn(&MazeState) -> Box<dyn Evaluator>>;
+type SearchFunction = Box<dyn Fn(&MazeState, &dyn Evaluator) -> usize>;
+
+struct StringAIPair {
+    name: String,
+    search: SearchFunction,
+    evaluator: EvaluatorFactory,
+}
+
+fn play_game(mut state: MazeState, ai: &StringAIPair) -> usize {
+    let evaluator = (ai.evaluator)(&state);
+    while !state.is_done() {
+        state.advance((ai.search)(&state, evaluator.as_ref()));
+    }
+    state.game_score
+}
+
+// ポイントの分布ごとに、同じ探索の平均スコアを比べる
+fn main() {
+    let ais = [
+        StringAIPair {
+            name: "greedyAction".to_string(),
+            search: Box::new(greedy_action),
+            evaluator: Box::new(|_| Box::new(ScoreOnly)),
+        },
+        StringAIPair {
+            name: "beamSearchAction".to_string(),
+            search: Box::new(|state, evaluator| beam_search_action(state, 5, 2, evaluator)),
+            evaluator: Box::new(|_| Box::new(ScoreOnly)),
+        },
+        StringAIPair {
+            name: "beamSearchAction(potential)".to_string(),
+            search: Box::new(|state, evaluator| beam_search_action(state, 5, 2, evaluator)),
+            evaluator: Box::new(|state| Box::new(NearbyPotential::new(state, 1.0))),
+        },
+        StringAIPair {
+            name: "chokudaiSearchAction".to_string(),
+            search: Box::new(|state, evaluator| chokudai_search_action(state, 1, 2, 2, evaluator)),
+            evaluator: Box::new(|_| Box::new(ScoreOnly)),
+        },
+    ];
+    let game_number = 20;
+    for distribution in standard_distributions() {
+        let config = InstanceConfig::new(distribution);
+        println!("{}", distribution);
+        println!("{}", config.generate(0));
+        for ai in &ais {
+            let total: usize = (0..game_number).map(|seed| play_game(config.generate(seed), ai)).sum();
+            println!("{}\t{}", ai.name, total / game_number as usize);
+        }
+        println!();
+    }
+}
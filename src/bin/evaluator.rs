@@ -0,0 +1,55 @@
+use search_algorithm::evaluator::{Evaluator, Linear, NearbyPotential, ReachableBound, ScoreOnly};
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::{beam_search_action, chokudai_search_action, greedy_action};
+
+// 評価関数は迷路ごとに作る(距離マップのキャッシュを持つため)
+type EvaluatorFactory = Box<dyn Fn(&MazeState) -> Box<dyn Evaluator>>;
+type SearchFunction = Box<dyn Fn(&MazeState, &dyn Evaluator) -> usize>;
+
+fn play_game(mut state: MazeState, search: &SearchFunction, evaluator: &dyn Evaluator) -> usize {
+    while !state.is_done() {
+        state.advance(search(&state, evaluator));
+    }
+    state.game_score
+}
+
+// 壁のある迷路で、探索と評価関数の組み合わせごとの平均スコアを比べる
+fn main() {
+    let evaluators: Vec<(&str, EvaluatorFactory)> = vec![
+        ("scoreOnly", Box::new(|_| Box::new(ScoreOnly))),
+        ("nearbyPotential", Box::new(|state| Box::new(NearbyPotential::new(state, 1.0)))),
+        ("reachableBound", Box::new(|state| Box::new(ReachableBound::new(state, 0.1)))),
+        (
+            "linear",
+            Box::new(|state| Box::new(Linear::new(state, [0.0, 1.0, 1.0, 0.05, 0.0, -0.1, 0.0, 0.0, 0.0]))),
+        ),
+    ];
+    let searches: Vec<(&str, SearchFunction)> = vec![
+        ("greedyAction", Box::new(greedy_action)),
+        ("beamSearchAction", Box::new(|state, evaluator| beam_search_action(state, 5, 2, evaluator))),
+        (
+            "chokudaiSearchAction",
+            Box::new(|state, evaluator| chokudai_search_action(state, 1, 2, 2, evaluator)),
+        ),
+    ];
+
+    let game_number = 20;
+    print!("search");
+    for (name, _) in &evaluators {
+        print!("\t{}", name);
+    }
+    println!();
+    for (search_name, search) in &searches {
+        print!("{}", search_name);
+        for (_, factory) in &evaluators {
+            let total: usize = (0..game_number)
+                .map(|seed| {
+                    let state = MazeState::with_walls(seed);
+                    play_game(state, search, factory(&state).as_ref())
+                })
+                .sum();
+            print!("\t{}", total / game_number as usize);
+        }
+        println!();
+    }
+}
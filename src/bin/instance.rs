@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use search_algorithm::evaluator::ScoreOnly;
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::{beam_search_action, chokudai_search_action, greedy_action};
+
+fn play_game(mut state: MazeState, action: impl Fn(&MazeState) -> usize) -> usize {
+    while !state.is_done() {
+        state.advance(action(&state));
+    }
+    state.game_score
+}
+
+// 盤面をテキストで保存し、読み込んだ盤面で探索のスコアを比べる
+//   instance save <seed> <path>  MazeState::new(seed)を保存する
+//   instance <path>              保存した盤面を読み込んで遊ぶ
+fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match &args[..] {
+        [command, seed, path] if command == "save" => {
+            let state = MazeState::new(seed.parse().expect("seed must be 0~255"));
+            state.save(Path::new(path))?;
+        }
+        [path] => {
+            let state = MazeState::load(Path::new(path))?;
+            println!("{}", state);
+            println!("greedyAction\t{}", play_game(state, |state| greedy_action(state, &ScoreOnly)));
+            println!("beamSearchAction\t{}", play_game(state, |state| beam_search_action(state, 5, 2, &ScoreOnly)));
+            println!(
+                "chokudaiSearchAction\t{}",
+                play_game(state, |state| chokudai_search_action(state, 1, 2, 2, &ScoreOnly))
+            );
+        }
+        _ => eprintln!("usage: instance save <seed> <path> | instance <path>"),
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use std::time::{Duration, Instant};
+
+use search_algorithm::alpha_beta::AlphaBetaSearcher;
+use search_algorithm::alternate_maze::{AlternateMazeState, END_TURN};
+use search_algorithm::minimax::score_evaluation;
+
+// 制限時間を変えながら同じ局面を反復深化で読み、読み切った深さを確かめる
+fn main() {
+    let state = AlternateMazeState::new(0);
+    println!("{}", state);
+    println!("time limit\tdepth\tnodes\telapsed\tvalue\tpv");
+    for time_limit_us in [10, 30, 100, 300, 1000] {
+        let time_limit = Duration::from_micros(time_limit_us);
+        let mut searcher = AlphaBetaSearcher::new(16);
+        let start = Instant::now();
+        let result = searcher.iterative_deepening(&state, time_limit, END_TURN, &score_evaluation);
+        let elapsed = start.elapsed();
+        println!(
+            "{:?}\t{}\t{}\t{:?}\t{}\t{:?}",
+            time_limit, result.depth, result.nodes, elapsed, result.value, result.principal_variation
+        );
+    }
+}
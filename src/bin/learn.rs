@@ -0,0 +1,48 @@
+use rand::prelude::*;
+use search_algorithm::evaluator::{Evaluator, Linear, NearbyPotential, ScoreOnly, FEATURE_NAMES};
+use search_algorithm::learning::{generate_samples, least_squares, mean_squared_error, SampleConfig};
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::beam_search_action;
+
+fn play_game(mut state: MazeState, evaluator: &dyn Evaluator) -> usize {
+    while !state.is_done() {
+        state.advance(beam_search_action(&state, 5, 2, evaluator));
+    }
+    state.game_score
+}
+
+// 幅の広いビームサーチの棋譜から線形の評価関数を学習し、学習に使っていない盤面で比べる
+// ビームサーチが選ばない局面も学習するように半分はランダムに動く(目標は各局面からのビームサーチの結果)
+fn main() {
+    let samples = generate_samples(
+        0..40,
+        MazeState::with_walls,
+        |state| Box::new(NearbyPotential::new(state, 1.0)),
+        SampleConfig { epsilon: 0.5, ..SampleConfig::default() },
+        &mut StdRng::seed_from_u64(0),
+    );
+    let (train, validation): (Vec<_>, Vec<_>) = samples.into_iter().partition(|sample| sample.seed < 30);
+    let weights = least_squares(&train, 1e-3);
+    println!("samples: train {} validation {}", train.len(), validation.len());
+    for (name, weight) in FEATURE_NAMES.iter().zip(&weights) {
+        println!("{}\t{:.4}", name, weight);
+    }
+    println!("train mse: {:.1}", mean_squared_error(&train, &weights));
+    println!("validation mse: {:.1}", mean_squared_error(&validation, &weights));
+
+    let seeds = 100..120;
+    let average = |evaluator: &dyn Fn(&MazeState) -> Box<dyn Evaluator>| {
+        let total: usize = seeds
+            .clone()
+            .map(|seed| {
+                let state = MazeState::with_walls(seed);
+                play_game(state, evaluator(&state).as_ref())
+            })
+            .sum();
+        total / seeds.len()
+    };
+    println!("beam search (5, 2) on unseen seeds");
+    println!("scoreOnly\t{}", average(&|_| Box::new(ScoreOnly)));
+    println!("nearbyPotential\t{}", average(&|state| Box::new(NearbyPotential::new(state, 1.0))));
+    println!("learned\t{}", average(&|state| Box::new(Linear::new(state, weights))));
+}
@@ -0,0 +1,96 @@
+use rand::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+use search_algorithm::auto_move_maze::AutoMoveMazeState;
+use search_algorithm::budget::Budget;
+use search_algorithm::cooling::{Exponential, Linear};
+use search_algorithm::local_search::{annealing, great_deluge, hill_climb, late_acceptance, threshold_accepting};
+use search_algorithm::move_policy::{Greedy, Lookahead, MovePolicy, NearestPoint, RandomWalk, TieBreak};
+use search_algorithm::neighborhood::{default_operators, AdaptiveNeighborhood};
+
+const CHARACTER_N: usize = 3;
+
+type State = AutoMoveMazeState;
+type AIFunction = Box<dyn Fn(&State, &Budget, &mut StdRng) -> State>;
+
+struct StringAIPair {
+    name: String,
+    function: AIFunction,
+}
+
+// 同じ近傍・同じ予算で各局所探索を動かし、平均スコアを比べる
+fn test_ai_score(ai: &StringAIPair, policy: &Rc<dyn MovePolicy>, budget: &Budget, game_number: usize) -> f64 {
+    let mut score_sum = 0;
+    for seed in 0..game_number {
+        let mut rng = StdRng::seed_from_u64(seed as u64);
+        let mut state = State::new(seed as u8, CHARACTER_N);
+        state.policy = policy.clone();
+        let best_state = (ai.function)(&state, budget, &mut rng);
+        score_sum += best_state.get_score(false);
+    }
+    score_sum as f64 / game_number as f64
+}
+
+fn main() {
+    let ais = vec![
+        StringAIPair {
+            name: "hillClimb".to_string(),
+            function: Box::new(|state, budget, rng| {
+                let mut neighborhood = AdaptiveNeighborhood::new(default_operators());
+                hill_climb(state, budget, &mut neighborhood, rng)
+            }),
+        },
+        StringAIPair {
+            name: "simulatedAnnealing".to_string(),
+            function: Box::new(|state, budget, rng| {
+                let mut neighborhood = AdaptiveNeighborhood::new(default_operators());
+                let schedule = Exponential {
+                    start_temp: 500_f64,
+                    end_temp: 10_f64,
+                };
+                annealing(state, budget, &schedule, &mut neighborhood, None, rng)
+            }),
+        },
+        StringAIPair {
+            name: "lateAcceptance".to_string(),
+            function: Box::new(|state, budget, rng| {
+                let mut neighborhood = AdaptiveNeighborhood::new(default_operators());
+                late_acceptance(state, budget, 50, &mut neighborhood, rng)
+            }),
+        },
+        StringAIPair {
+            name: "greatDeluge".to_string(),
+            function: Box::new(|state, budget, rng| {
+                let mut neighborhood = AdaptiveNeighborhood::new(default_operators());
+                great_deluge(state, budget, 0.05, &mut neighborhood, rng)
+            }),
+        },
+        StringAIPair {
+            name: "thresholdAccepting".to_string(),
+            function: Box::new(|state, budget, rng| {
+                let mut neighborhood = AdaptiveNeighborhood::new(default_operators());
+                let thresholds = Linear {
+                    start_temp: 100_f64,
+                    end_temp: 0_f64,
+                };
+                threshold_accepting(state, budget, &thresholds, &mut neighborhood, rng)
+            }),
+        },
+    ];
+
+    // キャラクターの動き方ごとに比べる。動き方によって1回の評価にかかる時間が違うので時間で揃える
+    let policies: Vec<(&str, Rc<dyn MovePolicy>)> = vec![
+        ("greedy", Rc::new(Greedy::default())),
+        ("greedyLastTie", Rc::new(Greedy { tie_break: TieBreak::Last })),
+        ("lookahead3", Rc::new(Lookahead { depth: 3, tie_break: TieBreak::First })),
+        ("nearestPoint", Rc::new(NearestPoint)),
+        ("randomWalk", Rc::new(RandomWalk { seed: 0 })),
+    ];
+    let budget = Budget::time(Duration::from_millis(100));
+    for (policy_name, policy) in &policies {
+        println!("policy: {}", policy_name);
+        for ai in &ais {
+            println!("Score of {}: {:.1}", ai.name, test_ai_score(ai, policy, &budget, 3));
+        }
+    }
+}
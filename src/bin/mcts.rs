@@ -0,0 +1,39 @@
+use rand::prelude::*;
+use search_algorithm::alternate_maze::AlternateMazeState;
+use search_algorithm::arena::{play_match, random_action, StringAIPair};
+use search_algorithm::budget::Budget;
+use search_algorithm::mcts::{mcts_action, Mcts, MctsConfig, RandomPlayout};
+
+type State = AlternateMazeState;
+
+// 先手は木を使い回すMCTS、後手は毎回作り直すMCTSで１局打ち、使い回した木の大きさを表示する
+fn play_with_tree_reuse(seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut state = State::new(seed);
+    let budget = Budget::iterations(1000);
+    let mut mcts = Mcts::new(&state, MctsConfig::default(), Box::new(RandomPlayout));
+    while !state.is_done() {
+        mcts.sync(&state);
+        println!("reused tree size: {}", mcts.tree_size());
+        let action = mcts.search(&budget, &mut rng);
+        print!("{}", mcts);
+        mcts.advance(action);
+        state.advance(action);
+        if state.is_done() {
+            break;
+        }
+        state.advance(mcts_action(&state, MctsConfig::default(), &budget, &mut rng));
+    }
+    println!("{}", state);
+    println!("1p: {:?}", state.first_player_status());
+}
+
+fn main() {
+    play_with_tree_reuse(0);
+
+    let mcts = StringAIPair::new("mctsAction", |state, rng| {
+        mcts_action(state, MctsConfig::default(), &Budget::iterations(1000), rng)
+    });
+    let random = StringAIPair::new("randomAction", random_action);
+    println!("{}", play_match(&mcts, &random, 50));
+}
@@ -0,0 +1,18 @@
+use search_algorithm::alternate_maze::AlternateMazeState;
+use search_algorithm::arena::{play_match, random_action, StringAIPair};
+use search_algorithm::minimax::{minimax_action, negamax_action, score_evaluation};
+
+type State = AlternateMazeState;
+
+fn main() {
+    let state = State::new(0);
+    println!("{}", state);
+    let result = minimax_action(&state, 4, &score_evaluation);
+    println!("minimax: action {} value {} pv {:?}", result.action, result.value, result.principal_variation);
+    let result = negamax_action(&state, 4, &score_evaluation);
+    println!("negamax: action {} value {} pv {:?}", result.action, result.value, result.principal_variation);
+
+    let minimax = StringAIPair::new("minimaxAction", |state, _| minimax_action(state, 4, &score_evaluation).action);
+    let random = StringAIPair::new("randomAction", random_action);
+    println!("{}", play_match(&minimax, &random, 50));
+}
@@ -0,0 +1,71 @@
+use rand::prelude::*;
+use search_algorithm::budget::Budget;
+use search_algorithm::evaluator::ScoreOnly;
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::greedy_action;
+use search_algorithm::q_learning::{q_learning_action, train, QLearningConfig, QTable, Schedule};
+use std::path::PathBuf;
+
+// 学習に使うseedはこれより小さいもの、比べるのはこれ以上のもの
+const TRAIN_SEEDS: u8 = 200;
+
+// マスの数の半分のターンで終わる、size * sizeの盤面
+// 全てのマスを回れないので、どこから取るかを選ばないとスコアが伸びない
+fn new_state(seed: u8, size: usize) -> MazeState {
+    MazeState::with_size(seed, size, size, size * size / 2)
+}
+
+fn average(size: usize, mut action: impl FnMut(&MazeState) -> usize) -> f64 {
+    let total: usize = (TRAIN_SEEDS..=u8::MAX)
+        .map(|seed| {
+            let mut state = new_state(seed, size);
+            while !state.is_done() {
+                state.advance(action(&state));
+            }
+            state.game_score
+        })
+        .sum();
+    total as f64 / (u8::MAX - TRAIN_SEEDS + 1) as f64
+}
+
+// 5x5と10x10の盤面でQ学習し、学習に使っていない盤面で貪欲法、ランダムと比べる
+// 引数を渡すと、学習した表をそのディレクトリに保存して読み直してから使う
+fn main() {
+    let directory = std::env::args().nth(1).map_or_else(std::env::temp_dir, PathBuf::from);
+    let mut rng = StdRng::seed_from_u64(0);
+    for size in [5, 10] {
+        let config = QLearningConfig {
+            epsilon: Schedule::Linear { start: 0.3, end: 0.0 },
+            alpha: Schedule::Exponential { start: 0.2, end: 0.01 },
+            gamma: Schedule::Constant(0.9),
+        };
+        let mut table = QTable::new(17);
+        let scores = train(
+            &mut table,
+            |rng| new_state(rng.gen_range(0..TRAIN_SEEDS), size),
+            &Budget::iterations(20_000),
+            &config,
+            &mut rng,
+        );
+        let window = scores.len() / 10;
+        let first = scores[..window].iter().sum::<usize>() as f64 / window as f64;
+        let last = scores[scores.len() - window..].iter().sum::<usize>() as f64 / window as f64;
+        println!("{}x{} training: first {:.1} last {:.1}", size, size, first, last);
+
+        let path = directory.join(format!("qtable_{}x{}.txt", size, size));
+        table.save(&path).expect("failed to save the table");
+        let table = QTable::load(&path).expect("failed to load the table");
+        println!("saved to {}", path.display());
+
+        let mut random_rng = StdRng::seed_from_u64(1);
+        println!("qLearning\t{:.1}", average(size, |state| q_learning_action(state, &table)));
+        println!("greedy\t{:.1}", average(size, |state| greedy_action(state, &ScoreOnly)));
+        println!(
+            "random\t{:.1}",
+            average(size, |state| {
+                let legal_actions = state.legal_actions();
+                legal_actions[random_rng.gen_range(0..legal_actions.len())]
+            })
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use search_algorithm::contest::score_output;
+use search_algorithm::maze::MazeState;
+
+// contestの出力を採点する
+//   scorer <盤面のファイル> <出力のファイル>
+// 全ての行動が正しければスコアを、そうでなければ不正だったターンと理由を表示する
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [instance, output] = &args[..] else {
+        eprintln!("usage: scorer <instance> <output>");
+        return ExitCode::FAILURE;
+    };
+    let state = match MazeState::load(Path::new(instance)) {
+        Ok(state) => state,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let output = match fs::read_to_string(output) {
+        Ok(output) => output,
+        Err(error) => {
+            eprintln!("{}: {}", output, error);
+            return ExitCode::FAILURE;
+        }
+    };
+    match score_output(state, &output) {
+        Ok(score) => {
+            println!("score: {}", score);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use rand::prelude::*;
+use search_algorithm::budget::Budget;
+use search_algorithm::duct::{duct_action, primitive_montecarlo_action};
+use search_algorithm::mcts::MctsConfig;
+use search_algorithm::simultaneous_maze::SimultaneousMazeState;
+use search_algorithm::two_player::WinningStatus;
+
+type State = SimultaneousMazeState;
+type AIFunction = Box<dyn Fn(&State, usize, &mut StdRng) -> usize>;
+
+struct StringAIPair {
+    name: String,
+    function: AIFunction,
+}
+
+fn random_action(state: &State, player: usize, rng: &mut StdRng) -> usize {
+    let legal_actions = state.legal_actions(player);
+    legal_actions[rng.gen_range(0..legal_actions.len())]
+}
+
+// seed_number個の盤面それぞれで立ち位置を入れ替えて2局ずつ打ち、aの勝率を返す
+fn test_win_rate(a: &StringAIPair, b: &StringAIPair, seed_number: usize) -> f64 {
+    let mut win_rate = 0.0;
+    for seed in 0..seed_number as u64 {
+        for a_player in [0, 1] {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut state = State::new(seed);
+            let ais = if a_player == 0 { [a, b] } else { [b, a] };
+            while !state.is_done() {
+                let actions = [(ais[0].function)(&state, 0, &mut rng), (ais[1].function)(&state, 1, &mut rng)];
+                state.advance(actions);
+            }
+            win_rate += match state.winning_status(a_player) {
+                WinningStatus::Win => 1.0,
+                WinningStatus::Draw => 0.5,
+                _ => 0.0,
+            };
+        }
+    }
+    win_rate /= (2 * seed_number) as f64;
+    println!("Winning rate of {} to {}:\t{}", a.name, b.name, win_rate);
+    win_rate
+}
+
+fn main() {
+    let mut state = State::new(0);
+    let mut rng = StdRng::seed_from_u64(0);
+    while !state.is_done() {
+        let actions = [random_action(&state, 0, &mut rng), random_action(&state, 1, &mut rng)];
+        state.advance(actions);
+    }
+    println!("{}", state);
+
+    let budget = Budget::iterations(1000);
+    let duct = StringAIPair {
+        name: "ductAction".to_string(),
+        function: Box::new(move |state, player, rng| duct_action(state, player, MctsConfig::default(), &budget, rng)),
+    };
+    let primitive = StringAIPair {
+        name: "primitiveMontecarloAction".to_string(),
+        function: Box::new(move |state, player, rng| primitive_montecarlo_action(state, player, &budget, rng)),
+    };
+    let random = StringAIPair {
+        name: "randomAction".to_string(),
+        function: Box::new(random_action),
+    };
+    test_win_rate(&duct, &random, 50);
+    test_win_rate(&primitive, &random, 50);
+    test_win_rate(&duct, &primitive, 50);
+}
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use search_algorithm::arena::{play_match, StringAIPair};
+use search_algorithm::budget::Budget;
+use search_algorithm::mcts::{mcts_action, thunder_action, MctsConfig};
+
+// 同じ持ち時間でThunderサーチとプレイアウトを使うMCTSを先後入れ替えて対戦させる
+fn main() {
+    for time_limit_us in [100, 300, 1000] {
+        let budget = Budget::time(Duration::from_micros(time_limit_us));
+        let thunder = StringAIPair::new(format!("thunder({:?})", budget.time_limit.unwrap()), move |state, rng| {
+            thunder_action(state, &budget, rng)
+        });
+        let mcts = StringAIPair::new(format!("mcts({:?})", budget.time_limit.unwrap()), move |state, rng| {
+            mcts_action(state, MctsConfig::default(), &budget, rng)
+        });
+        println!("{}", play_match(&thunder, &mcts, 50));
+    }
+}
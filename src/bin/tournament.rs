@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use search_algorithm::alpha_beta::{alpha_beta_action, iterative_deepening_action};
+use search_algorithm::arena::{random_action, StringAIPair};
+use search_algorithm::budget::Budget;
+use search_algorithm::mcts::{mcts_action, thunder_action, MctsConfig};
+use search_algorithm::minimax::{minimax_action, score_evaluation};
+use search_algorithm::tournament::{round_robin, Leaderboard};
+
+// 色々な設定のAIで総当たり戦を行い、Eloレートの順位表を表示する
+// 引数で結果ファイルを渡すと、途中で止めても続きから再開できる
+fn main() -> std::io::Result<()> {
+    let results_path = std::env::args().nth(1).map(PathBuf::from);
+    let mut ais = vec![StringAIPair::new("random", random_action)];
+    for depth in [1, 2, 3] {
+        ais.push(StringAIPair::new(format!("minimax({})", depth), move |state, _| {
+            minimax_action(state, depth, &score_evaluation).action
+        }));
+    }
+    ais.push(StringAIPair::new("alphaBeta(6)", |state, _| alpha_beta_action(state, 6, &score_evaluation).action));
+    ais.push(StringAIPair::new("iterativeDeepening(1ms)", |state, _| {
+        iterative_deepening_action(state, Duration::from_millis(1), &score_evaluation).action
+    }));
+    for number in [100, 1000] {
+        let budget = Budget::iterations(number);
+        ais.push(StringAIPair::new(format!("mcts({})", number), move |state, rng| {
+            mcts_action(state, MctsConfig::default(), &budget, rng)
+        }));
+        ais.push(StringAIPair::new(format!("thunder({})", number), move |state, rng| {
+            thunder_action(state, &budget, rng)
+        }));
+    }
+
+    let results = round_robin(&ais, 20, results_path.as_deref(), |result| {
+        println!("{}\t{}\t{}/{}/{}", result.name, result.opponent, result.wins, result.losses, result.draws);
+    })?;
+    let names: Vec<String> = ais.iter().map(|ai| ai.name.clone()).collect();
+    print!("{}", Leaderboard::new(&names, &results, 2.0));
+    Ok(())
+}
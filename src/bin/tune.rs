@@ -0,0 +1,134 @@
+use rand::prelude::*;
+use search_algorithm::alternate_maze::AlternateMazeState;
+use search_algorithm::auto_move_maze::AutoMoveMazeState;
+use search_algorithm::budget::Budget;
+use search_algorithm::cooling::Exponential;
+use search_algorithm::evaluator::NearbyPotential;
+use search_algorithm::local_search::annealing;
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::{beam_search_action, chokudai_search_action};
+use search_algorithm::mcts::{mcts_action, MctsConfig};
+use search_algorithm::neighborhood::{default_operators, AdaptiveNeighborhood};
+use search_algorithm::tuner::{tune, Config, ParamSpace, TunerConfig};
+use search_algorithm::two_player::WinningStatus;
+
+type Objective = fn(&Config, u64) -> f64;
+
+fn play_maze(seed: u64, action: impl Fn(&MazeState, &NearbyPotential) -> usize) -> f64 {
+    let mut state = MazeState::with_walls(seed as u8);
+    let evaluator = NearbyPotential::new(&state, 1.0);
+    while !state.is_done() {
+        state.advance(action(&state, &evaluator));
+    }
+    state.game_score as f64
+}
+
+fn beam_search(config: &Config, seed: u64) -> f64 {
+    play_maze(seed, |state, evaluator| {
+        beam_search_action(state, config.int("beam_width"), config.int("beam_depth"), evaluator)
+    })
+}
+
+fn chokudai_search(config: &Config, seed: u64) -> f64 {
+    play_maze(seed, |state, evaluator| {
+        chokudai_search_action(
+            state,
+            config.int("beam_width"),
+            config.int("beam_depth"),
+            config.int("beam_number"),
+            evaluator,
+        )
+    })
+}
+
+fn simulated_annealing(config: &Config, seed: u64) -> f64 {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let state = AutoMoveMazeState::new(seed as u8, 3);
+    // 終了温度は開始温度との比で調べ、温度が上がっていく設定を選ばないようにする
+    let start_temp = config.get("start_temp");
+    let schedule = Exponential {
+        start_temp,
+        end_temp: start_temp * config.get("end_ratio"),
+    };
+    let mut neighborhood = AdaptiveNeighborhood::new(default_operators());
+    let best_state = annealing(&state, &Budget::iterations(10_000), &schedule, &mut neighborhood, None, &mut rng);
+    best_state.get_score(false) as f64
+}
+
+// 先手と後手で1局ずつ打ち、既定の設定のMCTSに対する勝ち点(勝ち1、引き分け0.5)の平均
+fn mcts(config: &Config, seed: u64) -> f64 {
+    let mcts_config = MctsConfig {
+        c: config.get("c"),
+        expand_threshold: config.int("expand_threshold"),
+    };
+    let mut total = 0.0;
+    for mcts_player in [0, 1] {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut state = AlternateMazeState::new(seed);
+        let mut player = 0;
+        while !state.is_done() {
+            let action = if player == mcts_player {
+                mcts_action(&state, mcts_config, &Budget::iterations(100), &mut rng)
+            } else {
+                mcts_action(&state, MctsConfig::default(), &Budget::iterations(100), &mut rng)
+            };
+            state.advance(action);
+            player ^= 1;
+        }
+        total += match (state.first_player_status(), mcts_player) {
+            (WinningStatus::Win, 0) | (WinningStatus::Lose, 1) => 1.0,
+            (WinningStatus::Draw, _) => 0.5,
+            _ => 0.0,
+        };
+    }
+    total / 2.0
+}
+
+// 探索ごとにパラメータ空間を宣言し、ランダムサーチ + 逐次半減法で良い設定を探す
+fn main() {
+    let targets: Vec<(&str, ParamSpace, Objective)> = vec![
+        (
+            "beam_search",
+            ParamSpace::new().int("beam_width", 1, 10).int("beam_depth", 1, 4),
+            beam_search,
+        ),
+        (
+            "chokudai_search",
+            ParamSpace::new()
+                .int("beam_width", 1, 4)
+                .int("beam_depth", 1, 4)
+                .int("beam_number", 1, 4),
+            chokudai_search,
+        ),
+        (
+            "annealing",
+            ParamSpace::new()
+                .log_float("start_temp", 10.0, 2000.0)
+                .log_float("end_ratio", 0.0005, 0.5),
+            simulated_annealing,
+        ),
+        (
+            "mcts",
+            ParamSpace::new().log_float("c", 0.05, 5.0).int("expand_threshold", 1, 20),
+            mcts,
+        ),
+    ];
+    let target = std::env::args().nth(1);
+    let seeds: Vec<u64> = (0..64).collect();
+    let settings = TunerConfig {
+        max_evaluations: 240,
+        min_seeds: 2,
+        eta: 2,
+    };
+    for (name, space, objective) in targets {
+        if target.as_deref().is_some_and(|target| target != name) {
+            continue;
+        }
+        let mut rng = StdRng::seed_from_u64(0);
+        let result = tune(&space, &seeds, settings, objective, &mut rng);
+        println!("== {}", name);
+        print!("{}", result);
+        let best = result.best();
+        println!("best: {} (mean {:.2} over {} seeds)\n", best.config, best.mean(), best.scores.len());
+    }
+}
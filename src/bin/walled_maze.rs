@@ -0,0 +1,44 @@
+use search_algorithm::evaluator::ScoreOnly;
+use search_algorithm::maze::MazeState;
+use search_algorithm::maze_search::{beam_search_action, chokudai_search_action, greedy_action};
+
+type AIFunction = Box<dyn Fn(&MazeState) -> usize>;
+
+struct StringAIPair {
+    name: String,
+    function: AIFunction,
+}
+
+fn play_game(mut state: MazeState, ai: &StringAIPair) -> usize {
+    while !state.is_done() {
+        state.advance((ai.function)(&state));
+    }
+    state.game_score
+}
+
+// 壁の無い盤面と穴掘り法の迷路で、同じ探索の平均スコアを比べる
+fn main() {
+    println!("{}", MazeState::with_walls(0));
+
+    let ais = [
+        StringAIPair {
+            name: "greedyAction".to_string(),
+            function: Box::new(|state| greedy_action(state, &ScoreOnly)),
+        },
+        StringAIPair {
+            name: "beamSearchAction".to_string(),
+            function: Box::new(|state| beam_search_action(state, 5, 2, &ScoreOnly)),
+        },
+        StringAIPair {
+            name: "chokudaiSearchAction".to_string(),
+            function: Box::new(|state| chokudai_search_action(state, 1, 2, 2, &ScoreOnly)),
+        },
+    ];
+    let game_number = 20;
+    println!("ai\topen\twalled");
+    for ai in &ais {
+        let open: usize = (0..game_number).map(|seed| play_game(MazeState::new(seed), ai)).sum();
+        let walled: usize = (0..game_number).map(|seed| play_game(MazeState::with_walls(seed), ai)).sum();
+        println!("{}\t{}\t{}", ai.name, open / game_number as usize, walled / game_number as usize);
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+
+// 探索の打ち切り条件
+// 反復回数と制限時間のどちらかに達したら終わる
+#[derive(Clone, Copy, Debug)]
+pub struct Budget {
+    pub number: usize,
+    pub time_limit: Option<Duration>,
+}
+
+impl Budget {
+    pub fn iterations(number: usize) -> Self {
+        Self { number, time_limit: None }
+    }
+
+    pub fn time(time_limit: Duration) -> Self {
+        Self {
+            number: usize::MAX,
+            time_limit: Some(time_limit),
+        }
+    }
+
+    pub fn start(&self) -> BudgetTimer {
+        BudgetTimer {
+            budget: *self,
+            start: Instant::now(),
+            iteration: 0,
+        }
+    }
+}
+
+// 反復回数と経過時間それぞれの進み具合(0.0~1.0)
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    pub iteration: f64,
+    pub time: f64,
+}
+
+impl Progress {
+    pub fn overall(&self) -> f64 {
+        self.iteration.max(self.time).min(1.0)
+    }
+}
+
+pub struct BudgetTimer {
+    budget: Budget,
+    start: Instant,
+    iteration: usize,
+}
+
+impl BudgetTimer {
+    // 反復を１回進める。予算を使い切っていたらfalseを返す
+    pub fn step(&mut self) -> bool {
+        if self.iteration >= self.budget.number {
+            return false;
+        }
+        if let Some(time_limit) = self.budget.time_limit {
+            if self.start.elapsed() >= time_limit {
+                return false;
+            }
+        }
+        self.iteration += 1;
+        true
+    }
+
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub fn progress(&self) -> Progress {
+        let iteration = if self.budget.number == usize::MAX {
+            0.0
+        } else {
+            self.iteration as f64 / self.budget.number as f64
+        };
+        let time = match self.budget.time_limit {
+            Some(time_limit) => self.start.elapsed().as_secs_f64() / time_limit.as_secs_f64(),
+            None => 0.0,
+        };
+        Progress {
+            iteration: iteration.min(1.0),
+            time: time.min(1.0),
+        }
+    }
+}
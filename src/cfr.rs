@@ -0,0 +1,219 @@
+use crate::mcts::status_value;
+use crate::two_player::SimultaneousState;
+
+// 累積リグレットの正の部分に比例した混合戦略を返す(全て0以下なら一様)
+pub fn regret_matching(regrets: &[f64]) -> Vec<f64> {
+    let positive_sum: f64 = regrets.iter().map(|regret| regret.max(0.0)).sum();
+    if positive_sum > 0.0 {
+        regrets.iter().map(|regret| regret.max(0.0) / positive_sum).collect()
+    } else {
+        vec![1.0 / regrets.len() as f64; regrets.len()]
+    }
+}
+
+// 片方のプレイヤーの、ある局面での行動ごとの累積リグレットと戦略の累積
+#[derive(Default)]
+struct Decision {
+    actions: Vec<usize>,
+    regrets: Vec<f64>,
+    strategy_sum: Vec<f64>,
+}
+
+impl Decision {
+    fn new(actions: Vec<usize>) -> Self {
+        let len = actions.len();
+        Self {
+            actions,
+            regrets: vec![0.0; len],
+            strategy_sum: vec![0.0; len],
+        }
+    }
+
+    fn average_strategy(&self) -> Vec<f64> {
+        let total: f64 = self.strategy_sum.iter().sum();
+        if total > 0.0 {
+            self.strategy_sum.iter().map(|sum| sum / total).collect()
+        } else {
+            vec![1.0 / self.actions.len() as f64; self.actions.len()]
+        }
+    }
+}
+
+struct Node<S> {
+    state: S,
+    decisions: [Decision; 2],
+    children: Vec<Node<S>>, // children[i * (プレイヤー1の行動数) + j]。終局なら空
+}
+
+impl<S: SimultaneousState> Node<S> {
+    // 終局までの全ての局面を展開する
+    fn build(state: S) -> Self {
+        if state.is_done() {
+            return Self {
+                state,
+                decisions: Default::default(),
+                children: Vec::new(),
+            };
+        }
+        let decisions = [Decision::new(state.legal_actions(0)), Decision::new(state.legal_actions(1))];
+        let mut children = Vec::new();
+        for &action0 in &decisions[0].actions {
+            for &action1 in &decisions[1].actions {
+                let mut next_state = state.clone();
+                next_state.advance([action0, action1]);
+                children.push(Node::build(next_state));
+            }
+        }
+        Self {
+            state,
+            decisions,
+            children,
+        }
+    }
+
+    fn count_nodes(&self) -> usize {
+        1 + self.children.iter().map(|child| child.count_nodes()).sum::<usize>()
+    }
+
+    // reach[p]: プレイヤーpがこの局面に来るように打つ確率
+    // 現在の戦略でのプレイヤー0から見た価値(0.0~1.0)を返す
+    fn cfr(&mut self, reach: [f64; 2]) -> f64 {
+        if self.children.is_empty() {
+            return status_value(self.state.winning_status(0));
+        }
+        let strategies = [
+            regret_matching(&self.decisions[0].regrets),
+            regret_matching(&self.decisions[1].regrets),
+        ];
+        let n1 = strategies[1].len();
+        let mut values = vec![0.0; self.children.len()];
+        for (index, child) in self.children.iter_mut().enumerate() {
+            let (i, j) = (index / n1, index % n1);
+            values[index] = child.cfr([reach[0] * strategies[0][i], reach[1] * strategies[1][j]]);
+        }
+
+        // 行動ごとの、相手の戦略に対する期待値(それぞれのプレイヤーから見た値)
+        let action_values0: Vec<f64> = (0..strategies[0].len())
+            .map(|i| (0..n1).map(|j| strategies[1][j] * values[i * n1 + j]).sum())
+            .collect();
+        let action_values1: Vec<f64> = (0..n1)
+            .map(|j| (0..strategies[0].len()).map(|i| strategies[0][i] * (1.0 - values[i * n1 + j])).sum())
+            .collect();
+        let value: f64 = strategies[0].iter().zip(&action_values0).map(|(p, v)| p * v).sum();
+
+        for (player, action_values) in [action_values0, action_values1].iter().enumerate() {
+            let node_value = if player == 0 { value } else { 1.0 - value };
+            let decision = &mut self.decisions[player];
+            for (i, action_value) in action_values.iter().enumerate() {
+                decision.regrets[i] += reach[player ^ 1] * (action_value - node_value);
+                decision.strategy_sum[i] += reach[player] * strategies[player][i];
+            }
+        }
+        value
+    }
+
+    // 二人がそれぞれ相手のpolicyに最善応答したときの価値(それぞれのプレイヤーから見た値)を返す
+    fn best_responses(&self, policy: &mut dyn FnMut(&Node<S>, usize) -> Vec<f64>) -> [f64; 2] {
+        if self.children.is_empty() {
+            let value = status_value(self.state.winning_status(0));
+            return [value, 1.0 - value];
+        }
+        let strategies = [policy(self, 0), policy(self, 1)];
+        let n1 = strategies[1].len();
+        let values: Vec<[f64; 2]> = self.children.iter().map(|child| child.best_responses(policy)).collect();
+        let best0 = (0..strategies[0].len())
+            .map(|i| (0..n1).map(|j| strategies[1][j] * values[i * n1 + j][0]).sum::<f64>())
+            .fold(f64::MIN, f64::max);
+        let best1 = (0..n1)
+            .map(|j| (0..strategies[0].len()).map(|i| strategies[0][i] * values[i * n1 + j][1]).sum::<f64>())
+            .fold(f64::MIN, f64::max);
+        [best0, best1]
+    }
+}
+
+// 小さな同時手番ゲームの木を全て展開し、Counterfactual Regret Minimizationで均衡を求める
+// 局面ごとに二人がそれぞれリグレットマッチングで混合戦略を更新し、その平均が均衡に近づく
+pub struct CfrSolver<S> {
+    root: Node<S>,
+    pub iterations: usize,
+}
+
+impl<S: SimultaneousState> CfrSolver<S> {
+    pub fn new(state: &S) -> Self {
+        Self {
+            root: Node::build(state.clone()),
+            iterations: 0,
+        }
+    }
+
+    // 木に含まれる局面の数
+    pub fn tree_size(&self) -> usize {
+        self.root.count_nodes()
+    }
+
+    pub fn run(&mut self, iterations: usize) {
+        for _ in 0..iterations {
+            self.root.cfr([1.0, 1.0]);
+        }
+        self.iterations += iterations;
+    }
+
+    // ルートでのplayerの平均戦略(行動と確率の組)
+    pub fn root_strategy(&self, player: usize) -> Vec<(usize, f64)> {
+        let decision = &self.root.decisions[player];
+        decision.actions.iter().copied().zip(decision.average_strategy()).collect()
+    }
+
+    // 平均戦略のexploitability
+    pub fn exploitability(&self) -> f64 {
+        self.exploitability_of(|node, player| node.decisions[player].average_strategy())
+    }
+
+    // 局面とプレイヤーから合法手(legal_actionsの順)ごとの確率を返すpolicyのexploitability
+    // 二人それぞれが相手に最善応答したときに、均衡より得をする量の平均
+    pub fn policy_exploitability(&self, mut policy: impl FnMut(&S, usize) -> Vec<f64>) -> f64 {
+        self.exploitability_of(|node, player| policy(&node.state, player))
+    }
+
+    fn exploitability_of(&self, mut policy: impl FnMut(&Node<S>, usize) -> Vec<f64>) -> f64 {
+        let [best0, best1] = self.root.best_responses(&mut policy);
+        // 定和ゲーム(二人の価値の和が1)なので、均衡の価値によらずbest0 + best1 - 1が得をする量の合計
+        (best0 + best1 - 1.0) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simultaneous_maze::SimultaneousMazeState;
+
+    #[test]
+    fn regret_matching_uses_positive_regrets() {
+        assert_eq!(regret_matching(&[1.0, -2.0, 3.0]), vec![0.25, 0.0, 0.75]);
+        assert_eq!(regret_matching(&[0.0, 2.0]), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn regret_matching_is_uniform_without_positive_regrets() {
+        assert_eq!(regret_matching(&[-1.0, 0.0, -3.0, -0.5]), vec![0.25; 4]);
+    }
+
+    // cfrバイナリと同じ3 * 3、3ターンの盤面
+    #[test]
+    fn exploitability_approaches_zero() {
+        let state = SimultaneousMazeState::with_size(0, 3, 3, 3);
+        let mut solver = CfrSolver::new(&state);
+        let uniform = solver.policy_exploitability(|state, player| {
+            let n = state.legal_actions(player).len();
+            vec![1.0 / n as f64; n]
+        });
+        assert!(uniform > 0.1, "{}", uniform);
+        solver.run(1000);
+        let exploitability = solver.exploitability();
+        assert!((0.0..1e-3).contains(&exploitability), "{}", exploitability);
+        for player in [0, 1] {
+            let total: f64 = solver.root_strategy(player).iter().map(|&(_, probability)| probability).sum();
+            assert!((total - 1.0).abs() < 1e-9);
+        }
+    }
+}
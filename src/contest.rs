@@ -0,0 +1,159 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::coord::{Coord, DX, DY};
+use crate::maze::MazeState;
+
+// 行動を出力するときの文字。添字がactionに対応する(DX, DYと同じ並び)
+pub const ACTION_LETTERS: [char; 4] = ['R', 'L', 'D', 'U'];
+
+// stateからactionで最後まで打ったときの行動の列
+pub fn solve(mut state: MazeState, mut action: impl FnMut(&MazeState) -> usize) -> Vec<usize> {
+    let mut actions = Vec::new();
+    while !state.is_done() {
+        let chosen = action(&state);
+        actions.push(chosen);
+        state.advance(chosen);
+    }
+    actions
+}
+
+// 行動の列を1行の文字列にする
+pub fn format_actions(actions: &[usize]) -> String {
+    actions.iter().map(|&action| ACTION_LETTERS[action]).collect()
+}
+
+// 不正な出力だったターン(1から)と理由。行動が足りないときは最初の足りないターン
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoreError {
+    pub turn: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for ScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "turn {}: {}", self.turn, self.reason)
+    }
+}
+
+impl Error for ScoreError {}
+
+// 出力の行動を1つずつ確かめながら進め、最終スコアを返す
+// 空白と改行は読み飛ばす。行動はちょうど終了ターンまでの数だけ要る(途中で終わった出力もエラーにする)
+pub fn score_output(mut state: MazeState, output: &str) -> Result<usize, ScoreError> {
+    let expected = state.end_turn - state.turn;
+    let mut count = 0;
+    for letter in output.chars().filter(|c| !c.is_whitespace()) {
+        let turn = state.turn + 1;
+        count += 1;
+        if state.is_done() {
+            return Err(ScoreError {
+                turn,
+                reason: format!("more than {} actions", expected),
+            });
+        }
+        let action = ACTION_LETTERS.iter().position(|&c| c == letter).ok_or_else(|| ScoreError {
+            turn,
+            reason: format!("unknown action '{}' (expected one of R, L, D, U)", letter),
+        })?;
+        let next = Coord::new(state.character.y + DY[action], state.character.x + DX[action]);
+        if !state.is_passable(next) {
+            return Err(ScoreError {
+                turn,
+                reason: format!(
+                    "cannot move {} from ({}, {}) to ({}, {})",
+                    letter, state.character.y, state.character.x, next.y, next.x
+                ),
+            });
+        }
+        state.advance(action);
+    }
+    if !state.is_done() {
+        return Err(ScoreError {
+            turn: state.turn + 1,
+            reason: format!("expected {} actions, got {}", expected, count),
+        });
+    }
+    Ok(state.game_score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator::ScoreOnly;
+    use crate::maze_search::{beam_search_action, greedy_action};
+
+    // 2 * 3の盤面。キャラクターは左上で、右下は壁
+    const SMALL: &str = "end_turn 3\ncharacter 0 0\n@12\n34#\n";
+
+    fn small() -> MazeState {
+        MazeState::parse(SMALL).unwrap()
+    }
+
+    #[test]
+    fn format_actions_uses_direction_letters() {
+        assert_eq!(format_actions(&[0, 1, 2, 3]), "RLDU");
+        assert_eq!(format_actions(&[]), "");
+    }
+
+    #[test]
+    fn scores_a_valid_output() {
+        assert_eq!(score_output(small(), "RRL"), Ok(3));
+        assert_eq!(score_output(small(), "D\nR U\n"), Ok(8));
+    }
+
+    #[test]
+    fn rejects_unknown_letter() {
+        let error = score_output(small(), "RX").unwrap_err();
+        assert_eq!(error.turn, 2);
+        assert!(error.reason.contains("unknown action 'X'"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_moves_off_the_board_and_into_walls() {
+        let error = score_output(small(), "U").unwrap_err();
+        assert_eq!(error.turn, 1);
+        assert!(error.reason.contains("cannot move U from (0, 0) to (-1, 0)"), "{}", error);
+
+        let error = score_output(small(), "RRD").unwrap_err();
+        assert_eq!(error.turn, 3);
+        assert!(error.reason.contains("cannot move D from (0, 2) to (1, 2)"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_too_many_actions() {
+        let error = score_output(small(), "RLRL").unwrap_err();
+        assert_eq!(error.turn, 4);
+        assert!(error.reason.contains("more than 3 actions"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_too_few_actions() {
+        let error = score_output(small(), "RL").unwrap_err();
+        assert_eq!(error.turn, 3);
+        assert!(error.reason.contains("expected 3 actions, got 2"), "{}", error);
+    }
+
+    #[test]
+    fn solve_output_round_trips_through_the_scorer() {
+        for seed in [0, 3, 200] {
+            for state in [MazeState::new(seed), MazeState::with_walls(seed)] {
+                let actions = solve(state, |state| beam_search_action(state, 5, 2, &ScoreOnly));
+                assert_eq!(actions.len(), state.end_turn);
+                let mut played = state;
+                for &action in &actions {
+                    played.advance(action);
+                }
+                assert_eq!(score_output(state, &format_actions(&actions)), Ok(played.game_score));
+            }
+        }
+    }
+
+    // 動ける方向が1つしかない盤面でも最後まで打てる(動けない盤面はparseで弾く)
+    #[test]
+    fn solves_a_board_with_a_single_exit() {
+        let state = MazeState::parse("end_turn 5\ncharacter 0 0\n@#\n.#\n").unwrap();
+        let actions = solve(state, |state| greedy_action(state, &ScoreOnly));
+        assert_eq!(score_output(state, &format_actions(&actions)), Ok(0));
+    }
+}
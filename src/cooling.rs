@@ -0,0 +1,145 @@
+use std::fmt;
+
+use crate::budget::Progress;
+
+// 焼きなましの温度の下げ方
+pub trait CoolingSchedule {
+    fn temperature(&self, progress: Progress) -> f64;
+}
+
+// start_tempからend_tempへ直線的に下げる
+pub struct Linear {
+    pub start_temp: f64,
+    pub end_temp: f64,
+}
+
+impl CoolingSchedule for Linear {
+    fn temperature(&self, progress: Progress) -> f64 {
+        self.start_temp + (self.end_temp - self.start_temp) * progress.overall()
+    }
+}
+
+// 毎回一定の割合で下げる
+pub struct Exponential {
+    pub start_temp: f64,
+    pub end_temp: f64,
+}
+
+impl CoolingSchedule for Exponential {
+    fn temperature(&self, progress: Progress) -> f64 {
+        self.start_temp * (self.end_temp / self.start_temp).powf(progress.overall())
+    }
+}
+
+// T = start / (1 + c * ln(1 + (e - 1) * progress))
+// 序盤に大きく下げ、終盤はゆっくり下げる。最後にend_tempになるようにcを決める
+pub struct Logarithmic {
+    pub start_temp: f64,
+    pub end_temp: f64,
+}
+
+impl CoolingSchedule for Logarithmic {
+    fn temperature(&self, progress: Progress) -> f64 {
+        let c = self.start_temp / self.end_temp - 1.0;
+        let log = (1.0 + (std::f64::consts::E - 1.0) * progress.overall()).ln();
+        self.start_temp / (1.0 + c * log)
+    }
+}
+
+// 経過時間/制限時間だけから温度を決める(指数冷却)
+// 1回の遷移にかかる時間が読めないときに使う。Budgetに制限時間が無いとstart_tempのまま
+pub struct TimeBased {
+    pub start_temp: f64,
+    pub end_temp: f64,
+}
+
+impl CoolingSchedule for TimeBased {
+    fn temperature(&self, progress: Progress) -> f64 {
+        self.start_temp * (self.end_temp / self.start_temp).powf(progress.time)
+    }
+}
+
+// 全体をcycles個に区切り、区切りごとにinnerの温度を最初からやり直す
+// やり直すたびに温度全体をpeak_decay倍する
+pub struct Reheating {
+    inner: Box<dyn CoolingSchedule>,
+    cycles: usize,
+    peak_decay: f64,
+}
+
+impl Reheating {
+    pub fn new(inner: Box<dyn CoolingSchedule>, cycles: usize, peak_decay: f64) -> Self {
+        assert!(cycles >= 1, "cycles must be at least 1");
+        Self { inner, cycles, peak_decay }
+    }
+}
+
+impl CoolingSchedule for Reheating {
+    fn temperature(&self, progress: Progress) -> f64 {
+        let position = progress.overall() * self.cycles as f64;
+        let cycle = (position as usize).min(self.cycles - 1);
+        let local = (position - cycle as f64).min(1.0);
+        let local_progress = Progress {
+            iteration: local,
+            time: local,
+        };
+        self.inner.temperature(local_progress) * self.peak_decay.powi(cycle as i32)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct PhaseRecord {
+    trials: usize,
+    accepted: usize,
+    worsened: usize,
+    temperature_sum: f64,
+}
+
+// 進み具合をphases個に区切り、区切りごとの受理率を記録する
+// 温度の調整用
+pub struct AcceptanceLog {
+    records: Vec<PhaseRecord>,
+}
+
+impl AcceptanceLog {
+    pub fn new(phases: usize) -> Self {
+        assert!(phases > 0);
+        Self {
+            records: vec![PhaseRecord::default(); phases],
+        }
+    }
+
+    // worsened: 悪化する遷移を受理したか
+    pub fn record(&mut self, progress: Progress, temperature: f64, accepted: bool, worsened: bool) {
+        let phases = self.records.len();
+        let phase = ((progress.overall() * phases as f64) as usize).min(phases - 1);
+        let record = &mut self.records[phase];
+        record.trials += 1;
+        record.temperature_sum += temperature;
+        if accepted {
+            record.accepted += 1;
+            if worsened {
+                record.worsened += 1;
+            }
+        }
+    }
+}
+
+impl fmt::Display for AcceptanceLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "phase\ttrials\ttemp\taccept\tworse")?;
+        for (phase, record) in self.records.iter().enumerate() {
+            let trials = record.trials.max(1) as f64;
+            writeln!(
+                f,
+                "{}\t{}\t{:.2}\t{:.3}\t{:.3}",
+                phase,
+                record.trials,
+                record.temperature_sum / trials,
+                record.accepted as f64 / trials,
+                record.worsened as f64 / trials
+            )?;
+        }
+        Ok(())
+    }
+}
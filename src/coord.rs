@@ -0,0 +1,15 @@
+pub const DX: [isize; 4] = [1, -1, 0, 0];
+pub const DY: [isize; 4] = [0, 0, 1, -1];
+
+// 座標を保持する
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Coord {
+    pub y: isize,
+    pub x: isize,
+}
+
+impl Coord {
+    pub fn new(y: isize, x: isize) -> Self {
+        Self { y, x }
+    }
+}
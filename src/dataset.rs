@@ -0,0 +1,93 @@
+use std::io::{self, Write};
+
+use crate::coord::Coord;
+use crate::maze::{Cell, MazeState};
+
+// 外部で方策や価値のモデルを学習するためのデータ
+// 1行に1局面のJSONを書く(JSON Lines)。各行のキーは次の通り
+//   seed        盤面を作ったseed
+//   turn        局面のターン(0から)
+//   h, w        使っている盤面の大きさ
+//   character   キャラクターの位置 [y, x]
+//   score       局面までに得たスコア
+//   board       h個の長さwの文字列。数字はポイント、.は何も無い床、#は壁(キャラクターのマスは.)
+//   action      探索が選んだ行動。0: 右, 1: 左, 2: 下, 3: 上
+//   final_score その局面から探索で最後まで打ったときの最終スコア
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub seed: u8,
+    pub turn: usize,
+    pub h: usize,
+    pub w: usize,
+    pub character: Coord,
+    pub score: usize,
+    pub board: Vec<String>,
+    pub action: usize,
+    pub final_score: usize,
+}
+
+impl Record {
+    pub fn to_json(&self) -> String {
+        let board: Vec<String> = self.board.iter().map(|row| format!("\"{}\"", row)).collect();
+        format!(
+            "{{\"seed\":{},\"turn\":{},\"h\":{},\"w\":{},\"character\":[{},{}],\"score\":{},\"board\":[{}],\"action\":{},\"final_score\":{}}}",
+            self.seed,
+            self.turn,
+            self.h,
+            self.w,
+            self.character.y,
+            self.character.x,
+            self.score,
+            board.join(","),
+            self.action,
+            self.final_score
+        )
+    }
+}
+
+fn board_rows(state: &MazeState) -> Vec<String> {
+    state.cells[..state.h]
+        .iter()
+        .map(|row| {
+            row[..state.w]
+                .iter()
+                .map(|cell| match cell {
+                    Cell::Wall => '#',
+                    Cell::Floor => '.',
+                    Cell::Point(point) => char::from_digit(*point as u32, 10).unwrap(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// stateからactionで最後まで打ち、通った全ての局面を記録する
+pub fn record_game(seed: u8, mut state: MazeState, mut action: impl FnMut(&MazeState) -> usize) -> Vec<Record> {
+    let mut records = Vec::new();
+    while !state.is_done() {
+        let chosen = action(&state);
+        records.push(Record {
+            seed,
+            turn: state.turn,
+            h: state.h,
+            w: state.w,
+            character: state.character,
+            score: state.game_score,
+            board: board_rows(&state),
+            action: chosen,
+            final_score: 0,
+        });
+        state.advance(chosen);
+    }
+    for record in &mut records {
+        record.final_score = state.game_score;
+    }
+    records
+}
+
+pub fn write_jsonl(records: &[Record], writer: &mut impl Write) -> io::Result<()> {
+    for record in records {
+        writeln!(writer, "{}", record.to_json())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::coord::{Coord, DX, DY};
+use crate::maze::{MazeState, H, W};
+
+const UNREACHABLE: usize = usize::MAX;
+
+// 始点から各マスへの、壁を避けた最短距離
+#[derive(Clone)]
+pub struct DistanceMap {
+    distances: [[usize; W]; H],
+}
+
+impl DistanceMap {
+    pub fn new(state: &MazeState, start: Coord) -> Self {
+        Self::from_sources(state, &[start])
+    }
+
+    // 複数の始点のうち最も近いものからの距離(多始点BFS)
+    pub fn from_sources(state: &MazeState, sources: &[Coord]) -> Self {
+        let mut distances = [[UNREACHABLE; W]; H];
+        let mut queue = VecDeque::new();
+        for &source in sources {
+            if state.is_passable(source) && distances[source.y as usize][source.x as usize] == UNREACHABLE {
+                distances[source.y as usize][source.x as usize] = 0;
+                queue.push_back(source);
+            }
+        }
+        while let Some(coord) = queue.pop_front() {
+            let distance = distances[coord.y as usize][coord.x as usize];
+            for action in 0..4 {
+                let next = Coord::new(coord.y + DY[action], coord.x + DX[action]);
+                if state.is_passable(next) && distances[next.y as usize][next.x as usize] == UNREACHABLE {
+                    distances[next.y as usize][next.x as usize] = distance + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        Self { distances }
+    }
+
+    // たどり着けないマスならNone
+    pub fn get(&self, coord: Coord) -> Option<usize> {
+        let distance = self.distances[coord.y as usize][coord.x as usize];
+        (distance != UNREACHABLE).then_some(distance)
+    }
+}
+
+// 始点ごとの距離マップを初めて使うときに計算して覚えておく
+// 壁はゲーム中に変わらないので、同じ迷路の状態であればターンが進んでも使い回せる
+pub struct DistanceCache {
+    state: MazeState,
+    maps: RefCell<Vec<Option<Rc<DistanceMap>>>>,
+}
+
+impl DistanceCache {
+    pub fn new(state: &MazeState) -> Self {
+        Self {
+            state: *state,
+            maps: RefCell::new(vec![None; H * W]),
+        }
+    }
+
+    pub fn distance_from(&self, start: Coord) -> Rc<DistanceMap> {
+        let index = start.y as usize * W + start.x as usize;
+        let mut maps = self.maps.borrow_mut();
+        maps[index]
+            .get_or_insert_with(|| Rc::new(DistanceMap::new(&self.state, start)))
+            .clone()
+    }
+}
+
+// ポイントの価値を距離で割り引くときの倍率。game_scoreもこの倍率をかけて足す
+pub const POTENTIAL_SCALE: usize = 100;
+
+// 残っているポイントpを、距離dに応じてp / (d + 1)^2に割り引いて足し合わせる
+pub fn point_potential(state: &MazeState, map: &DistanceMap) -> usize {
+    let mut potential = 0;
+    for (y, row) in state.cells.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            let point = cell.point();
+            if point == 0 {
+                continue;
+            }
+            if let Some(distance) = map.get(Coord::new(y as isize, x as isize)) {
+                potential += point * POTENTIAL_SCALE / ((distance + 1) * (distance + 1));
+            }
+        }
+    }
+    potential
+}
+
+// 得たスコアに、キャラクターの近くに残っている価値の高いポイントの分を加えた評価
+pub fn distance_aware_score(state: &MazeState, cache: &DistanceCache) -> usize {
+    state.game_score * POTENTIAL_SCALE + point_potential(state, &cache.distance_from(state.character))
+}
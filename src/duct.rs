@@ -0,0 +1,209 @@
+use rand::prelude::*;
+
+use crate::budget::Budget;
+use crate::mcts::{status_value, ChildStats, MctsConfig};
+use crate::two_player::SimultaneousState;
+
+fn random_action<S: SimultaneousState>(state: &S, player: usize, rng: &mut dyn RngCore) -> usize {
+    let legal_actions = state.legal_actions(player);
+    legal_actions[rng.gen_range(0..legal_actions.len())]
+}
+
+// 二人ともランダムに終局まで打ち、playerから見た価値(0.0~1.0)を返す
+pub fn playout_value<S: SimultaneousState>(state: &S, player: usize, rng: &mut dyn RngCore) -> f64 {
+    let mut now_state = state.clone();
+    while !now_state.is_done() {
+        let actions = [random_action(&now_state, 0, rng), random_action(&now_state, 1, rng)];
+        now_state.advance(actions);
+    }
+    status_value(now_state.winning_status(player))
+}
+
+// 片方のプレイヤーの行動ごとの統計
+#[derive(Default)]
+struct ActionStats {
+    actions: Vec<usize>,
+    w: Vec<f64>, // このプレイヤーから見た価値の合計
+    n: Vec<usize>,
+}
+
+impl ActionStats {
+    fn new(actions: Vec<usize>) -> Self {
+        let len = actions.len();
+        Self {
+            actions,
+            w: vec![0.0; len],
+            n: vec![0; len],
+        }
+    }
+
+    // まだ試していない行動があればそれを、無ければUCB1が最大の行動の番号を選ぶ
+    fn select(&self, total: usize, c: f64) -> usize {
+        if let Some(index) = self.n.iter().position(|&n| n == 0) {
+            return index;
+        }
+        let ln_n = (total as f64).ln();
+        let ucb1 = |i: usize| self.w[i] / self.n[i] as f64 + c * (ln_n / self.n[i] as f64).sqrt();
+        (0..self.actions.len()).max_by(|&a, &b| ucb1(a).total_cmp(&ucb1(b))).unwrap()
+    }
+
+    fn update(&mut self, index: usize, value: f64) {
+        self.w[index] += value;
+        self.n[index] += 1;
+    }
+}
+
+// 子ノードは二人の行動の組ごとに持ち、統計はプレイヤーごとに別々に持つ
+struct Node<S> {
+    state: S,
+    n: usize,
+    stats: [ActionStats; 2],
+    children: Vec<Option<Node<S>>>, // children[i * (プレイヤー1の行動数) + j]
+}
+
+impl<S: SimultaneousState> Node<S> {
+    fn new(state: S) -> Self {
+        Self {
+            state,
+            n: 0,
+            stats: Default::default(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_expanded(&self) -> bool {
+        !self.children.is_empty()
+    }
+
+    fn expand(&mut self) {
+        self.stats = [
+            ActionStats::new(self.state.legal_actions(0)),
+            ActionStats::new(self.state.legal_actions(1)),
+        ];
+        let size = self.stats[0].actions.len() * self.stats[1].actions.len();
+        self.children = (0..size).map(|_| None).collect();
+    }
+
+    // プレイヤー0から見た価値を返す
+    fn evaluate(&mut self, config: &MctsConfig, rng: &mut dyn RngCore) -> f64 {
+        let value = if self.state.is_done() {
+            status_value(self.state.winning_status(0))
+        } else if !self.is_expanded() {
+            let value = playout_value(&self.state, 0, rng);
+            if self.n + 1 >= config.expand_threshold {
+                self.expand();
+            }
+            value
+        } else {
+            // 二人がそれぞれ自分の統計だけを見て行動を選ぶ
+            let i = self.stats[0].select(self.n, config.c);
+            let j = self.stats[1].select(self.n, config.c);
+            let actions = [self.stats[0].actions[i], self.stats[1].actions[j]];
+            let state = &self.state;
+            let child = self.children[i * self.stats[1].actions.len() + j].get_or_insert_with(|| {
+                let mut next_state = state.clone();
+                next_state.advance(actions);
+                Node::new(next_state)
+            });
+            let value = child.evaluate(config, rng);
+            self.stats[0].update(i, value);
+            self.stats[1].update(j, 1.0 - value);
+            value
+        };
+        self.n += 1;
+        value
+    }
+}
+
+// Decoupled UCT
+// 同時手番の局面で、二人がそれぞれ相手の行動を知らずにUCB1で行動を選ぶ
+pub struct Duct<S> {
+    root: Node<S>,
+    pub config: MctsConfig,
+}
+
+impl<S: SimultaneousState> Duct<S> {
+    pub fn new(state: &S, config: MctsConfig) -> Self {
+        Self {
+            root: Node::new(state.clone()),
+            config,
+        }
+    }
+
+    // 予算の分だけ探索する
+    pub fn search(&mut self, budget: &Budget, rng: &mut impl Rng) {
+        assert!(!self.root.state.is_done(), "cannot choose an action in a finished game");
+        if !self.root.is_expanded() {
+            self.root.expand();
+        }
+        let mut timer = budget.start();
+        while timer.step() {
+            self.root.evaluate(&self.config, rng);
+        }
+    }
+
+    // playerが最も多く試した行動
+    pub fn best_action(&self, player: usize) -> usize {
+        self.root_stats(player).iter().max_by_key(|stats| stats.visits).unwrap().action
+    }
+
+    // ルートでのplayerの行動ごとの試行回数と、playerから見た勝率
+    pub fn root_stats(&self, player: usize) -> Vec<ChildStats> {
+        let stats = &self.root.stats[player];
+        (0..stats.actions.len())
+            .map(|i| ChildStats {
+                action: stats.actions[i],
+                visits: stats.n[i],
+                win_rate: if stats.n[i] == 0 {
+                    0.5
+                } else {
+                    stats.w[i] / stats.n[i] as f64
+                },
+            })
+            .collect()
+    }
+}
+
+// 毎回新しい木でDUCTを行い、playerの行動を返す
+pub fn duct_action<S: SimultaneousState>(
+    state: &S,
+    player: usize,
+    config: MctsConfig,
+    budget: &Budget,
+    rng: &mut impl Rng,
+) -> usize {
+    let mut duct = Duct::new(state, config);
+    duct.search(budget, rng);
+    duct.best_action(player)
+}
+
+// 原始モンテカルロ法
+// playerの行動を順番に試し、相手の行動とその後は全てランダムに打って平均の価値が最も高い行動を選ぶ
+pub fn primitive_montecarlo_action<S: SimultaneousState>(
+    state: &S,
+    player: usize,
+    budget: &Budget,
+    rng: &mut impl Rng,
+) -> usize {
+    let legal_actions = state.legal_actions(player);
+    let mut w = vec![0.0; legal_actions.len()];
+    let mut n = vec![0; legal_actions.len()];
+    let mut timer = budget.start();
+    while timer.step() {
+        let index = (timer.iteration() - 1) % legal_actions.len();
+        let mut actions = [0; 2];
+        actions[player] = legal_actions[index];
+        actions[player ^ 1] = random_action(state, player ^ 1, rng);
+        let mut next_state = state.clone();
+        next_state.advance(actions);
+        w[index] += playout_value(&next_state, player, rng);
+        n[index] += 1;
+    }
+    let best = (0..legal_actions.len())
+        .max_by(|&a, &b| {
+            let mean = |i: usize| if n[i] == 0 { 0.0 } else { w[i] / n[i] as f64 };
+            mean(a).total_cmp(&mean(b))
+        })
+        .unwrap();
+    legal_actions[best]
+}
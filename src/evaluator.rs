@@ -0,0 +1,163 @@
+use crate::coord::Coord;
+use crate::distance::{point_potential, DistanceCache, POTENTIAL_SCALE};
+use crate::maze::MazeState;
+
+// 探索中の盤面の良さ。大きいほど良い
+pub trait Evaluator {
+    fn evaluate(&self, state: &MazeState) -> f64;
+}
+
+// 得たスコアだけで評価する
+pub struct ScoreOnly;
+
+impl Evaluator for ScoreOnly {
+    fn evaluate(&self, state: &MazeState) -> f64 {
+        state.game_score as f64
+    }
+}
+
+// スコアに、近くに残っているポイントを距離で割り引いた合計(point_potential)のweight倍を足す
+pub struct NearbyPotential {
+    cache: DistanceCache,
+    pub weight: f64,
+}
+
+impl NearbyPotential {
+    // stateと同じ迷路でだけ使える
+    pub fn new(state: &MazeState, weight: f64) -> Self {
+        Self {
+            cache: DistanceCache::new(state),
+            weight,
+        }
+    }
+}
+
+impl Evaluator for NearbyPotential {
+    fn evaluate(&self, state: &MazeState) -> f64 {
+        let potential = point_potential(state, &self.cache.distance_from(state.character)) as f64 / POTENTIAL_SCALE as f64;
+        state.game_score as f64 + self.weight * potential
+    }
+}
+
+// 残りターンで取れるポイントの楽観的な上界
+// 残りターン数以内の距離にあるポイントを、大きい順に残りターン数だけ取れたとみなす
+pub fn reachable_bound(state: &MazeState, cache: &DistanceCache) -> usize {
+    let remaining_turn = state.end_turn - state.turn;
+    let map = cache.distance_from(state.character);
+    let mut points: Vec<usize> = state
+        .cells
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, cell)| (y, x, cell.point())))
+        .filter(|&(y, x, point)| {
+            let distance = map.get(Coord::new(y as isize, x as isize));
+            point > 0 && distance.is_some_and(|distance| distance <= remaining_turn)
+        })
+        .map(|(_, _, point)| point)
+        .collect();
+    points.sort_unstable_by(|a, b| b.cmp(a));
+    points.iter().take(remaining_turn).sum()
+}
+
+// スコアに、reachable_boundのweight倍を足す
+pub struct ReachableBound {
+    cache: DistanceCache,
+    pub weight: f64,
+}
+
+impl ReachableBound {
+    // stateと同じ迷路でだけ使える
+    pub fn new(state: &MazeState, weight: f64) -> Self {
+        Self {
+            cache: DistanceCache::new(state),
+            weight,
+        }
+    }
+}
+
+impl Evaluator for ReachableBound {
+    fn evaluate(&self, state: &MazeState) -> f64 {
+        state.game_score as f64 + self.weight * reachable_bound(state, &self.cache) as f64
+    }
+}
+
+// features()でポイントを数える範囲(最短距離)
+pub const RADIUS: usize = 5;
+
+// Linearで使う特徴量の数と名前。名前はfeatures()の並びと同じ
+pub const FEATURE_N: usize = 9;
+pub const FEATURE_NAMES: [&str; FEATURE_N] = [
+    "bias",
+    "score",
+    "potential",
+    "reachable_bound",
+    "legal_actions",
+    "nearest_point",
+    "points_within_radius",
+    "nearest_nine",
+    "remaining_turns",
+];
+
+// 盤面の特徴量
+// nearest_point, nearest_nineは最も近いポイント、9のマスまでの距離(無ければ残りターン数)
+pub fn features(state: &MazeState, cache: &DistanceCache) -> [f64; FEATURE_N] {
+    let map = cache.distance_from(state.character);
+    let remaining_turn = state.end_turn - state.turn;
+    let mut nearest_point = remaining_turn;
+    let mut nearest_nine = remaining_turn;
+    let mut points_within_radius = 0;
+    for (y, row) in state.cells.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            let point = cell.point();
+            if point == 0 {
+                continue;
+            }
+            if let Some(distance) = map.get(Coord::new(y as isize, x as isize)) {
+                nearest_point = nearest_point.min(distance);
+                if point == 9 {
+                    nearest_nine = nearest_nine.min(distance);
+                }
+                if distance <= RADIUS {
+                    points_within_radius += point;
+                }
+            }
+        }
+    }
+    [
+        1.0,
+        state.game_score as f64,
+        point_potential(state, &map) as f64 / POTENTIAL_SCALE as f64,
+        reachable_bound(state, cache) as f64,
+        state.legal_actions().len() as f64,
+        nearest_point as f64,
+        points_within_radius as f64,
+        nearest_nine as f64,
+        remaining_turn as f64,
+    ]
+}
+
+// 特徴量と重みの内積で評価する
+pub struct Linear {
+    cache: DistanceCache,
+    pub weights: [f64; FEATURE_N],
+}
+
+impl Linear {
+    // stateと同じ迷路でだけ使える
+    pub fn new(state: &MazeState, weights: [f64; FEATURE_N]) -> Self {
+        Self {
+            cache: DistanceCache::new(state),
+            weights,
+        }
+    }
+}
+
+impl Evaluator for Linear {
+    fn evaluate(&self, state: &MazeState) -> f64 {
+        features(state, &self.cache)
+            .iter()
+            .zip(&self.weights)
+            .map(|(feature, weight)| feature * weight)
+            .sum()
+    }
+}
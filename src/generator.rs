@@ -0,0 +1,158 @@
+use rand::prelude::*;
+use std::fmt;
+
+use crate::coord::Coord;
+use crate::maze::{Cell, MazeState, H, W};
+
+// 床に置くポイントの分布
+#[derive(Clone, Copy, Debug)]
+pub enum PointDistribution {
+    Uniform,                                    // 0~9が一様(MazeState::with_sizeと同じ盤面)
+    Sparse { density: f64 },                    // density の確率で1~9、それ以外は0
+    Clustered { hotspots: usize, sigma: f64 },  // 中心からの距離に応じたガウス分布の山をhotspots個置く
+    Gradient,                                   // ランダムに選んだ角から対角に向かって大きくなる
+    Checkerboard,                               // 市松模様の白マスだけに5~9
+    RareHigh { probability: f64 },              // ほとんどが0~2で、probabilityの確率で9
+}
+
+impl fmt::Display for PointDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PointDistribution::Uniform => write!(f, "uniform"),
+            PointDistribution::Sparse { density } => write!(f, "sparse({})", density),
+            PointDistribution::Clustered { hotspots, sigma } => write!(f, "clustered({}, {})", hotspots, sigma),
+            PointDistribution::Gradient => write!(f, "gradient"),
+            PointDistribution::Checkerboard => write!(f, "checkerboard"),
+            PointDistribution::RareHigh { probability } => write!(f, "rareHigh({})", probability),
+        }
+    }
+}
+
+// マス(y, x)とrngからポイントを決める関数
+type PointFunction = Box<dyn Fn(usize, usize, &mut StdRng) -> usize>;
+
+// 盤面の作り方。左上のh * wを使い、それより外側は壁にする
+#[derive(Clone, Copy, Debug)]
+pub struct InstanceConfig {
+    pub h: usize,
+    pub w: usize,
+    pub distribution: PointDistribution,
+}
+
+impl Default for InstanceConfig {
+    fn default() -> Self {
+        Self {
+            h: H,
+            w: W,
+            distribution: PointDistribution::Uniform,
+        }
+    }
+}
+
+impl InstanceConfig {
+    pub fn new(distribution: PointDistribution) -> Self {
+        Self {
+            distribution,
+            ..Self::default()
+        }
+    }
+
+    // seedから盤面を作る。同じ設定とseedなら同じ盤面になる
+    pub fn generate(&self, seed: u8) -> MazeState {
+        assert!(0 < self.h && self.h <= H && 0 < self.w && self.w <= W);
+        match self.distribution {
+            PointDistribution::Sparse { density } => {
+                assert!((0.0..=1.0).contains(&density), "density must be in [0, 1], got {}", density)
+            }
+            PointDistribution::Clustered { sigma, .. } => {
+                assert!(sigma.is_finite() && sigma > 0.0, "sigma must be positive, got {}", sigma)
+            }
+            PointDistribution::RareHigh { probability } => {
+                assert!((0.0..=1.0).contains(&probability), "probability must be in [0, 1], got {}", probability)
+            }
+            PointDistribution::Uniform | PointDistribution::Gradient | PointDistribution::Checkerboard => {}
+        }
+        let mut rng: StdRng = SeedableRng::from_seed([seed; 32]);
+        let character = Coord::new(rng.gen_range(0..self.h) as isize, rng.gen_range(0..self.w) as isize);
+        let point = self.point_function(&mut rng);
+        let mut cells = [[Cell::Wall; W]; H];
+        for (y, row) in cells.iter_mut().enumerate().take(self.h) {
+            for (x, cell) in row.iter_mut().enumerate().take(self.w) {
+                if y as isize == character.y && x as isize == character.x {
+                    *cell = Cell::Floor;
+                    continue;
+                }
+                *cell = match point(y, x, &mut rng) {
+                    0 => Cell::Floor,
+                    point => Cell::Point(point.min(9)),
+                };
+            }
+        }
+        MazeState::from_cells(cells, self.h, self.w, character)
+    }
+
+    // マス(y, x)のポイントを返す関数。分布ごとに盤面全体で使う値(山の中心など)はここで決める
+    fn point_function(&self, rng: &mut StdRng) -> PointFunction {
+        let (h, w) = (self.h, self.w);
+        match self.distribution {
+            PointDistribution::Uniform => Box::new(|_, _, rng| rng.gen_range(0..10)),
+            PointDistribution::Sparse { density } => Box::new(move |_, _, rng| {
+                if rng.gen_bool(density) {
+                    rng.gen_range(1..10)
+                } else {
+                    0
+                }
+            }),
+            PointDistribution::Clustered { hotspots, sigma } => {
+                let centers: Vec<(f64, f64)> = (0..hotspots)
+                    .map(|_| (rng.gen_range(0..h) as f64, rng.gen_range(0..w) as f64))
+                    .collect();
+                // 最も近い山の高さの確率でポイントを置き、高いほど大きなポイントにする
+                Box::new(move |y, x, rng| {
+                    let intensity = centers
+                        .iter()
+                        .map(|&(cy, cx)| {
+                            let distance2 = (y as f64 - cy).powi(2) + (x as f64 - cx).powi(2);
+                            (-distance2 / (2.0 * sigma * sigma)).exp()
+                        })
+                        .fold(0.0, f64::max);
+                    if rng.gen_bool(intensity) {
+                        (9.0 * intensity).ceil() as usize
+                    } else {
+                        0
+                    }
+                })
+            }
+            PointDistribution::Gradient => {
+                let (flip_y, flip_x) = (rng.gen_bool(0.5), rng.gen_bool(0.5));
+                // 角からの位置に比例した値に、-1~1のノイズを加える
+                Box::new(move |y, x, rng| {
+                    let y = if flip_y { h - 1 - y } else { y };
+                    let x = if flip_x { w - 1 - x } else { x };
+                    let ratio = (y + x) as f64 / (h + w - 2).max(1) as f64;
+                    (9.0 * ratio + rng.gen_range(-1.0..1.0)).round().clamp(0.0, 9.0) as usize
+                })
+            }
+            PointDistribution::Checkerboard => Box::new(|y, x, rng| if (y + x) % 2 == 0 { rng.gen_range(5..10) } else { 0 }),
+            PointDistribution::RareHigh { probability } => Box::new(move |_, _, rng| {
+                if rng.gen_bool(probability) {
+                    9
+                } else {
+                    rng.gen_range(0..3)
+                }
+            }),
+        }
+    }
+}
+
+// ベンチマークでよく使う分布の一覧
+pub fn standard_distributions() -> Vec<PointDistribution> {
+    vec![
+        PointDistribution::Uniform,
+        PointDistribution::Sparse { density: 0.1 },
+        PointDistribution::Clustered { hotspots: 4, sigma: 3.0 },
+        PointDistribution::Gradient,
+        PointDistribution::Checkerboard,
+        PointDistribution::RareHigh { probability: 0.02 },
+    ]
+}
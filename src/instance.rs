@@ -0,0 +1,335 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::coord::Coord;
+use crate::maze::{Cell, MazeState, H, W};
+
+// 盤面をテキストで保存する形式
+// 先頭に"キー 値"のヘッダ行を並べ、その後に盤面をto_stringと同じ文字で書く
+//   end_turn 100      終了ターン(必須)
+//   character 3 5     キャラクターの位置 y x(必須)
+//   turn 0            現在のターン(省略すると0)
+//   score 0           得たスコア(省略すると0)
+// 盤面の行は全て同じ長さで、数字はポイント(0は.と同じ)、.は床、#は壁、@はキャラクター
+// @はちょうど1つで、characterの位置と一致していなければならない。また@の隣に壁でないマスが要る
+// ヘッダの間の空行と#!で始まる行はコメントとして読み飛ばす。盤面の途中には置けない(盤面の後の空行はよい)
+
+// 読み込みに失敗した行(1から)と理由
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseMazeError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseMazeError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseMazeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseMazeError {}
+
+fn parse_numbers<const N: usize>(line: usize, key: &str, values: &[&str]) -> Result<[usize; N], ParseMazeError> {
+    if values.len() != N {
+        return Err(ParseMazeError::new(
+            line,
+            format!("{} needs {} value(s), found {}", key, N, values.len()),
+        ));
+    }
+    let mut numbers = [0; N];
+    for (number, value) in numbers.iter_mut().zip(values) {
+        *number = value
+            .parse()
+            .map_err(|_| ParseMazeError::new(line, format!("{}: '{}' is not a non-negative integer", key, value)))?;
+    }
+    Ok(numbers)
+}
+
+impl MazeState {
+    pub fn parse(text: &str) -> Result<Self, ParseMazeError> {
+        let mut end_turn = None;
+        let mut character = None;
+        let mut turn = (0, 0); // (行, 値)
+        let mut score = 0;
+        let mut rows: Vec<(usize, &str)> = Vec::new();
+        let mut blank_after_board = None; // 盤面の後に最初に出てきた空行
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim_end();
+            if rows.is_empty() {
+                if line.is_empty() || line.starts_with("#!") {
+                    continue;
+                }
+            } else if line.is_empty() {
+                blank_after_board.get_or_insert(line_number);
+                continue;
+            } else if line.starts_with("#!") {
+                return Err(ParseMazeError::new(line_number, "comment inside the board"));
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let is_header = fields[0].starts_with(|c: char| c.is_ascii_alphabetic());
+            if !is_header {
+                if let Some(blank_line) = blank_after_board {
+                    return Err(ParseMazeError::new(blank_line, "blank line inside the board"));
+                }
+                rows.push((line_number, line));
+                continue;
+            }
+            if !rows.is_empty() {
+                return Err(ParseMazeError::new(line_number, format!("header '{}' after the board", fields[0])));
+            }
+            match fields[0] {
+                "end_turn" => end_turn = Some(parse_numbers::<1>(line_number, fields[0], &fields[1..])?[0]),
+                "character" => character = Some((line_number, parse_numbers::<2>(line_number, fields[0], &fields[1..])?)),
+                "turn" => turn = (line_number, parse_numbers::<1>(line_number, fields[0], &fields[1..])?[0]),
+                "score" => score = parse_numbers::<1>(line_number, fields[0], &fields[1..])?[0],
+                key => return Err(ParseMazeError::new(line_number, format!("unknown header '{}'", key))),
+            }
+        }
+        let last_line = text.lines().count().max(1);
+        let end_turn = end_turn.ok_or_else(|| ParseMazeError::new(last_line, "missing end_turn header"))?;
+        let (character_line, [y, x]) = character.ok_or_else(|| ParseMazeError::new(last_line, "missing character header"))?;
+        let (turn_line, turn) = turn;
+        if turn > end_turn {
+            return Err(ParseMazeError::new(turn_line, format!("turn {} is after end_turn {}", turn, end_turn)));
+        }
+
+        let h = rows.len();
+        if h == 0 {
+            return Err(ParseMazeError::new(last_line, "missing board"));
+        }
+        if h > H {
+            return Err(ParseMazeError::new(rows[H].0, format!("board has {} rows, at most {} are allowed", h, H)));
+        }
+        let w = rows[0].1.chars().count();
+        if w > W {
+            return Err(ParseMazeError::new(rows[0].0, format!("row has {} cells, at most {} are allowed", w, W)));
+        }
+        let mut cells = [[Cell::Wall; W]; H];
+        let mut marker = None;
+        for (y, &(line_number, row)) in rows.iter().enumerate() {
+            let length = row.chars().count();
+            if length != w {
+                return Err(ParseMazeError::new(
+                    line_number,
+                    format!("row has {} cells, expected {} like the first row", length, w),
+                ));
+            }
+            for (x, c) in row.chars().enumerate() {
+                cells[y][x] = match c {
+                    '.' | '0' => Cell::Floor,
+                    '#' => Cell::Wall,
+                    '1'..='9' => Cell::Point(c.to_digit(10).unwrap() as usize),
+                    '@' => {
+                        if marker.is_some() {
+                            return Err(ParseMazeError::new(line_number, format!("second '@' at column {}", x + 1)));
+                        }
+                        marker = Some(Coord::new(y as isize, x as isize));
+                        Cell::Floor
+                    }
+                    _ => return Err(ParseMazeError::new(line_number, format!("unexpected '{}' at column {}", c, x + 1))),
+                };
+            }
+        }
+        let character = Coord::new(y as isize, x as isize);
+        if y >= h || x >= w {
+            return Err(ParseMazeError::new(
+                character_line,
+                format!("character ({}, {}) is outside the {}x{} board", y, x, h, w),
+            ));
+        }
+        match marker {
+            None => return Err(ParseMazeError::new(rows[0].0, "board has no '@'")),
+            Some(marker) if marker != character => {
+                return Err(ParseMazeError::new(
+                    character_line,
+                    format!("character ({}, {}) does not match '@' at ({}, {})", y, x, marker.y, marker.x),
+                ));
+            }
+            Some(_) => {}
+        }
+
+        // 動けるマスが無いとゲームを進められない
+        let mut state = Self::try_from_cells(cells, h, w, character).ok_or_else(|| {
+            ParseMazeError::new(character_line, format!("character ({}, {}) has no cell to move to", y, x))
+        })?;
+        state.end_turn = end_turn;
+        state.turn = turn;
+        state.game_score = score;
+        Ok(state)
+    }
+
+    // parseで読める形式の文字列
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "end_turn {}\ncharacter {} {}\nturn {}\nscore {}\n",
+            self.end_turn, self.character.y, self.character.x, self.turn, self.game_score
+        );
+        for y in 0..self.h {
+            for x in 0..self.w {
+                text.push(if self.character == Coord::new(y as isize, x as isize) {
+                    '@'
+                } else {
+                    match self.cells[y][x] {
+                        Cell::Wall => '#',
+                        Cell::Floor => '.',
+                        Cell::Point(point) => char::from_digit(point as u32, 10).unwrap(),
+                    }
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    // 読めない形式ならInvalidDataのエラーにする
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), error)))
+    }
+}
+
+impl FromStr for MazeState {
+    type Err = ParseMazeError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(text: &str) -> ParseMazeError {
+        MazeState::parse(text).err().expect("parse should fail")
+    }
+
+    fn assert_same(a: &MazeState, b: &MazeState) {
+        assert!(a.cells == b.cells);
+        assert_eq!((a.h, a.w, a.character), (b.h, b.w, b.character));
+        assert_eq!((a.turn, a.end_turn, a.game_score), (b.turn, b.end_turn, b.game_score));
+    }
+
+    #[test]
+    fn round_trip() {
+        for seed in [0, 1, 77, 255] {
+            let mut played = MazeState::with_walls(seed);
+            played.advance(played.legal_actions()[0]);
+            for state in [MazeState::new(seed), MazeState::with_size(seed, 7, 4, 12), played] {
+                let parsed: MazeState = state.to_text().parse().unwrap();
+                assert_same(&parsed, &state);
+                assert_eq!(parsed.to_text(), state.to_text());
+            }
+        }
+    }
+
+    #[test]
+    fn fixture_matches_its_seed() {
+        let state = MazeState::parse(include_str!("../fixtures/walled_0.txt")).unwrap();
+        assert_same(&state, &MazeState::with_walls(0));
+    }
+
+    #[test]
+    fn header_after_board() {
+        let error = parse_error("end_turn 5\n@1\ncharacter 0 0\n");
+        assert_eq!(error.line, 3);
+        assert!(error.message.contains("after the board"), "{}", error);
+    }
+
+    #[test]
+    fn blank_line_inside_board() {
+        let error = parse_error("end_turn 5\ncharacter 0 0\n@1\n\n23\n");
+        assert_eq!(error.line, 4);
+        assert!(error.message.contains("blank line inside the board"), "{}", error);
+    }
+
+    #[test]
+    fn comment_inside_board() {
+        let error = parse_error("end_turn 5\ncharacter 0 0\n@1\n#! note\n23\n");
+        assert_eq!(error.line, 4);
+        assert!(error.message.contains("comment inside the board"), "{}", error);
+    }
+
+    #[test]
+    fn blank_lines_around_headers_and_after_board() {
+        let state = MazeState::parse("#! note\n\nend_turn 5\n\ncharacter 0 0\n#! note\n@1\n23\n\n\n").unwrap();
+        assert_eq!((state.h, state.w), (2, 2));
+    }
+
+    #[test]
+    fn trapped_character() {
+        for text in ["end_turn 5\ncharacter 0 0\n@#\n##\n", "end_turn 5\ncharacter 0 0\n@\n"] {
+            let error = parse_error(text);
+            assert_eq!(error.line, 2);
+            assert!(error.message.contains("has no cell to move to"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn second_marker() {
+        let error = parse_error("end_turn 5\ncharacter 0 0\n@1\n2@\n");
+        assert_eq!(error.line, 4);
+        assert!(error.message.contains("second '@' at column 2"), "{}", error);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let error = parse_error("end_turn 5\ncharacter 0 0\n@12\n34\n");
+        assert_eq!(error.line, 4);
+        assert!(error.message.contains("row has 2 cells, expected 3"), "{}", error);
+    }
+
+    #[test]
+    fn character_does_not_match_marker() {
+        let error = parse_error("end_turn 5\ncharacter 0 1\n@1\n");
+        assert_eq!(error.line, 2);
+        assert!(error.message.contains("does not match '@' at (0, 0)"), "{}", error);
+    }
+
+    #[test]
+    fn missing_end_turn() {
+        let error = parse_error("character 0 0\n@1\n");
+        assert!(error.message.contains("missing end_turn"), "{}", error);
+    }
+
+    #[test]
+    fn turn_after_end_turn() {
+        let error = parse_error("end_turn 5\nturn 6\ncharacter 0 0\n@1\n");
+        assert_eq!(error.line, 2);
+        assert!(error.message.contains("turn 6 is after end_turn 5"), "{}", error);
+    }
+
+    #[test]
+    fn oversized_board() {
+        let mut text = String::from("end_turn 5\ncharacter 0 0\n@\n");
+        for _ in 0..H {
+            text += "1\n";
+        }
+        let error = parse_error(&text);
+        assert_eq!(error.line, 3 + H);
+        assert!(error.message.contains("at most 30"), "{}", error);
+
+        let wide = format!("end_turn 5\ncharacter 0 0\n@{}\n", "1".repeat(W));
+        let error = parse_error(&wide);
+        assert_eq!(error.line, 3);
+        assert!(error.message.contains("row has 31 cells, at most 30"), "{}", error);
+    }
+}
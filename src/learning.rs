@@ -0,0 +1,186 @@
+use rand::prelude::*;
+
+use crate::distance::DistanceCache;
+use crate::evaluator::{features, Evaluator, FEATURE_N};
+use crate::maze::MazeState;
+use crate::maze_search::beam_search_action;
+
+// 学習データの1件。ある局面の特徴量と、その局面からビームサーチで最後まで打って得た最終スコア
+#[derive(Clone, Debug)]
+pub struct Sample {
+    pub seed: u8,
+    pub turn: usize,
+    pub features: [f64; FEATURE_N],
+    pub target: f64,
+}
+
+// 学習データを作るときの打ち方
+#[derive(Clone, Copy, Debug)]
+pub struct SampleConfig {
+    pub beam_width: usize,
+    pub beam_depth: usize,
+    pub epsilon: f64, // この確率でビームサーチの代わりにランダムに動く
+}
+
+impl Default for SampleConfig {
+    fn default() -> Self {
+        Self {
+            beam_width: 20,
+            beam_depth: 3,
+            epsilon: 0.0,
+        }
+    }
+}
+
+// stateからビームサーチだけで最後まで打ったときの最終スコア
+fn beam_rollout(mut state: MazeState, config: &SampleConfig, evaluator: &dyn Evaluator) -> usize {
+    while !state.is_done() {
+        state.advance(beam_search_action(&state, config.beam_width, config.beam_depth, evaluator));
+    }
+    state.game_score
+}
+
+// 各seedの盤面をビームサーチで最後まで打ち、通った全ての局面を学習データにする
+// new_stateはseedから盤面を作り、evaluatorは盤面ごとに作る
+// epsilonの確率でランダムに動き、ビームサーチが選ばないような局面も混ぜる
+// 目標はどの局面でも、その局面からビームサーチだけで打ったときの最終スコアにする
+pub fn generate_samples(
+    seeds: impl Iterator<Item = u8>,
+    new_state: impl Fn(u8) -> MazeState,
+    evaluator: impl Fn(&MazeState) -> Box<dyn Evaluator>,
+    config: SampleConfig,
+    rng: &mut impl Rng,
+) -> Vec<Sample> {
+    let mut samples = Vec::new();
+    for seed in seeds {
+        let mut state = new_state(seed);
+        let cache = DistanceCache::new(&state);
+        let evaluator = evaluator(&state);
+        let mut game = Vec::new();
+        while !state.is_done() {
+            // epsilonが0なら、この対局の最終スコアがそのまま目標になる
+            let target = (config.epsilon > 0.0).then(|| beam_rollout(state, &config, evaluator.as_ref()));
+            game.push((state.turn, features(&state, &cache), target));
+            let action = if rng.gen_range(0.0..1.0) < config.epsilon {
+                let legal_actions = state.legal_actions();
+                legal_actions[rng.gen_range(0..legal_actions.len())]
+            } else {
+                beam_search_action(&state, config.beam_width, config.beam_depth, evaluator.as_ref())
+            };
+            state.advance(action);
+        }
+        for (turn, features, target) in game {
+            samples.push(Sample {
+                seed,
+                turn,
+                features,
+                target: target.unwrap_or(state.game_score) as f64,
+            });
+        }
+    }
+    samples
+}
+
+// a x = bを部分ピボット付きのガウスの消去法で解く
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    for column in 0..n {
+        let pivot = (column..n).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs())).unwrap();
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+        if a[column][column].abs() < 1e-12 {
+            continue;
+        }
+        let (upper, lower) = a.split_at_mut(column + 1);
+        let pivot_row = &upper[column];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[column] / pivot_row[column];
+            for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(column) {
+                *value -= factor * pivot_value;
+            }
+            b[column + 1 + offset] -= factor * b[column];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        if a[row][row].abs() < 1e-12 {
+            continue;
+        }
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    x
+}
+
+// 二乗誤差 + ridge * |w|^2 を最小にする重みを正規方程式で求める
+// ridgeは特徴量どうしが強く相関していても解が安定するように足す
+pub fn least_squares(samples: &[Sample], ridge: f64) -> [f64; FEATURE_N] {
+    let mut xtx = vec![vec![0.0; FEATURE_N]; FEATURE_N];
+    let mut xty = vec![0.0; FEATURE_N];
+    for sample in samples {
+        for (i, row) in xtx.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value += sample.features[i] * sample.features[j];
+            }
+            xty[i] += sample.features[i] * sample.target;
+        }
+    }
+    for (i, row) in xtx.iter_mut().enumerate() {
+        row[i] += ridge;
+    }
+    let mut weights = [0.0; FEATURE_N];
+    weights.copy_from_slice(&solve(xtx, xty));
+    weights
+}
+
+// 重みで予測した値と目標との平均二乗誤差
+pub fn mean_squared_error(samples: &[Sample], weights: &[f64; FEATURE_N]) -> f64 {
+    let total: f64 = samples
+        .iter()
+        .map(|sample| {
+            let prediction: f64 = sample.features.iter().zip(weights).map(|(feature, weight)| feature * weight).sum();
+            (prediction - sample.target).powi(2)
+        })
+        .sum();
+    total / samples.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_needs_pivoting() {
+        // 左上が0なので、行を入れ替えないと解けない
+        let a = vec![vec![0.0, 2.0, 1.0], vec![1.0, 1.0, 0.0], vec![3.0, 0.0, 2.0]];
+        let x = solve(a, vec![7.0, 3.0, 9.0]);
+        for (value, expected) in x.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((value - expected).abs() < 1e-9, "{:?}", x);
+        }
+    }
+
+    #[test]
+    fn least_squares_recovers_noiseless_weights() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut weights = [0.0; FEATURE_N];
+        weights.iter_mut().for_each(|weight| *weight = rng.gen_range(-5.0..5.0));
+        let samples: Vec<Sample> = (0..200)
+            .map(|turn| {
+                let mut features = [0.0; FEATURE_N];
+                features.iter_mut().for_each(|feature| *feature = rng.gen_range(-10.0..10.0));
+                let target = features.iter().zip(&weights).map(|(feature, weight)| feature * weight).sum();
+                Sample {
+                    seed: 0,
+                    turn,
+                    features,
+                    target,
+                }
+            })
+            .collect();
+        let learned = least_squares(&samples, 0.0);
+        for (value, expected) in learned.iter().zip(&weights) {
+            assert!((value - expected).abs() < 1e-9, "{:?} != {:?}", learned, weights);
+        }
+        assert!(mean_squared_error(&samples, &learned) < 1e-12);
+    }
+}
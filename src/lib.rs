@@ -0,0 +1,28 @@
+pub mod alpha_beta;
+pub mod alternate_maze;
+pub mod arena;
+pub mod auto_move_maze;
+pub mod budget;
+pub mod cfr;
+pub mod contest;
+pub mod cooling;
+pub mod coord;
+pub mod dataset;
+pub mod distance;
+pub mod duct;
+pub mod evaluator;
+pub mod generator;
+pub mod instance;
+pub mod learning;
+pub mod local_search;
+pub mod maze;
+pub mod maze_search;
+pub mod mcts;
+pub mod minimax;
+pub mod move_policy;
+pub mod neighborhood;
+pub mod q_learning;
+pub mod simultaneous_maze;
+pub mod tournament;
+pub mod tuner;
+pub mod two_player;
@@ -0,0 +1,183 @@
+use rand::Rng;
+
+use crate::auto_move_maze::AutoMoveMazeState;
+use crate::budget::Budget;
+use crate::cooling::{AcceptanceLog, CoolingSchedule};
+use crate::neighborhood::Neighborhood;
+
+// 配置を変える単一解の局所探索
+// どれも同じNeighborhoodとBudgetを受け取り、見つけた中で最良の配置を返す
+
+pub fn hill_climb(
+    state: &AutoMoveMazeState,
+    budget: &Budget,
+    neighborhood: &mut impl Neighborhood,
+    rng: &mut impl Rng,
+) -> AutoMoveMazeState {
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    let mut best_score = now_state.get_score(false);
+    let mut timer = budget.start();
+    while timer.step() {
+        let mut next_state = now_state.clone();
+        let operator_id = neighborhood.apply(&mut next_state, rng);
+        let next_score = next_state.get_score(false);
+        let improved = next_score > best_score;
+        neighborhood.feedback(operator_id, improved);
+        if improved {
+            best_score = next_score;
+            now_state = next_state;
+        }
+    }
+    now_state
+}
+
+pub fn annealing(
+    state: &AutoMoveMazeState,
+    budget: &Budget,
+    schedule: &dyn CoolingSchedule,
+    neighborhood: &mut impl Neighborhood,
+    mut log: Option<&mut AcceptanceLog>,
+    rng: &mut impl Rng,
+) -> AutoMoveMazeState {
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    let mut best_score = now_state.get_score(false);
+    let mut best_state = now_state.clone();
+    let mut now_score = best_score;
+
+    let mut timer = budget.start();
+    while timer.step() {
+        let mut next_state = now_state.clone();
+        let operator_id = neighborhood.apply(&mut next_state, rng);
+        let next_score = next_state.get_score(false);
+        if next_score > best_score {
+            best_score = next_score;
+            best_state = next_state.clone();
+        }
+        let progress = timer.progress();
+        let temp = schedule.temperature(progress);
+
+        // メトロポリス基準: 改善なら必ず、悪化ならexp(差分/温度)の確率で受理する
+        let delta = next_score as f64 - now_score as f64;
+        let accepted = delta >= 0.0 || rng.gen_range(0.0..1.0) < (delta / temp).exp();
+        neighborhood.feedback(operator_id, next_score > now_score);
+        if let Some(log) = log.as_deref_mut() {
+            log.record(progress, temp, accepted, delta < 0.0);
+        }
+        if accepted {
+            now_score = next_score;
+            now_state = next_state;
+        }
+    }
+    best_state
+}
+
+// Late Acceptance Hill Climbing
+// history_length回前の現在スコア以上なら受理する。温度の代わりに履歴の長さだけを調整すればよい
+pub fn late_acceptance(
+    state: &AutoMoveMazeState,
+    budget: &Budget,
+    history_length: usize,
+    neighborhood: &mut impl Neighborhood,
+    rng: &mut impl Rng,
+) -> AutoMoveMazeState {
+    assert!(history_length > 0);
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    let mut now_score = now_state.get_score(false);
+    let mut best_score = now_score;
+    let mut best_state = now_state.clone();
+    let mut history = vec![now_score; history_length];
+
+    let mut timer = budget.start();
+    while timer.step() {
+        let mut next_state = now_state.clone();
+        let operator_id = neighborhood.apply(&mut next_state, rng);
+        let next_score = next_state.get_score(false);
+        neighborhood.feedback(operator_id, next_score > now_score);
+        if next_score > best_score {
+            best_score = next_score;
+            best_state = next_state.clone();
+        }
+
+        let slot = timer.iteration() % history_length;
+        if next_score >= history[slot] || next_score >= now_score {
+            now_score = next_score;
+            now_state = next_state;
+        }
+        history[slot] = now_score;
+    }
+    best_state
+}
+
+// Great Deluge
+// 水位より上のスコアなら受理し、水位は反復ごとにrain_speedずつ上がる
+pub fn great_deluge(
+    state: &AutoMoveMazeState,
+    budget: &Budget,
+    rain_speed: f64,
+    neighborhood: &mut impl Neighborhood,
+    rng: &mut impl Rng,
+) -> AutoMoveMazeState {
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    let mut now_score = now_state.get_score(false);
+    let mut best_score = now_score;
+    let mut best_state = now_state.clone();
+    let mut level = now_score as f64;
+
+    let mut timer = budget.start();
+    while timer.step() {
+        let mut next_state = now_state.clone();
+        let operator_id = neighborhood.apply(&mut next_state, rng);
+        let next_score = next_state.get_score(false);
+        neighborhood.feedback(operator_id, next_score > now_score);
+        if next_score > best_score {
+            best_score = next_score;
+            best_state = next_state.clone();
+        }
+
+        if next_score as f64 >= level || next_score >= now_score {
+            now_score = next_score;
+            now_state = next_state;
+        }
+        level += rain_speed;
+    }
+    best_state
+}
+
+// Threshold Accepting
+// 悪化幅がしきい値以下なら受理する。しきい値の下げ方は焼きなましの温度と同じCoolingScheduleで決める
+pub fn threshold_accepting(
+    state: &AutoMoveMazeState,
+    budget: &Budget,
+    thresholds: &dyn CoolingSchedule,
+    neighborhood: &mut impl Neighborhood,
+    rng: &mut impl Rng,
+) -> AutoMoveMazeState {
+    let mut now_state = state.clone();
+    now_state.init(rng);
+    let mut now_score = now_state.get_score(false);
+    let mut best_score = now_score;
+    let mut best_state = now_state.clone();
+
+    let mut timer = budget.start();
+    while timer.step() {
+        let mut next_state = now_state.clone();
+        let operator_id = neighborhood.apply(&mut next_state, rng);
+        let next_score = next_state.get_score(false);
+        neighborhood.feedback(operator_id, next_score > now_score);
+        if next_score > best_score {
+            best_score = next_score;
+            best_state = next_state.clone();
+        }
+
+        let threshold = thresholds.temperature(timer.progress());
+        if next_score as f64 >= now_score as f64 - threshold {
+            now_score = next_score;
+            now_state = next_state;
+        }
+    }
+    best_state
+}
@@ -0,0 +1,228 @@
+use rand::prelude::*;
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::coord::{Coord, DX, DY};
+
+pub const H: usize = 30;
+pub const W: usize = 30;
+pub const END_TURN: usize = 100;
+
+// 迷路のマスの種類
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Floor,
+    Wall,
+    Point(usize), // 1~9のポイントが置かれた床
+}
+
+impl Cell {
+    // マスに置かれたポイント(無ければ0)
+    pub fn point(&self) -> usize {
+        match self {
+            Cell::Point(point) => *point,
+            _ => 0,
+        }
+    }
+}
+
+// 一人ゲームの例
+// 1ターンに上下左右四方向のいずれかに1マスずつ進む。壁のマスには進めない。
+// 床にあるポイントを踏むと自身のスコアとなり、床のポイントが消える。
+// end_turn(既定はEND_TURN)の時点のスコアを高くすることが目的
+#[derive(Clone, Copy)]
+pub struct MazeState {
+    pub cells: [[Cell; W]; H],
+    pub h: usize, // 使っている範囲の高さ(それより下は全て壁)
+    pub w: usize, // 使っている範囲の幅(それより右は全て壁)
+    pub turn: usize, // 現在のターン
+    pub end_turn: usize, // このターンになったら終了
+    pub character: Coord,
+    pub game_score: usize, // ゲーム上で実際に得たスコア
+    pub evaluated_score: f64, // 探索で使う評価値(Evaluatorが付ける)
+    pub first_action: isize,
+}
+
+impl Ord for MazeState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.evaluated_score.total_cmp(&other.evaluated_score)
+    }
+}
+
+impl PartialOrd for MazeState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for MazeState {}
+
+impl PartialEq for MazeState {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl MazeState {
+    // 壁の無い盤面
+    pub fn new(seed: u8) -> Self {
+        Self::with_size(seed, H, W, END_TURN)
+    }
+
+    // 左上のh * wだけを使い、end_turnターンで終わる壁の無い盤面。それより外側は全て壁にする
+    // 小さい盤面でEND_TURNのままだと、ランダムに歩くだけでほとんどのポイントを取れてしまう
+    pub fn with_size(seed: u8, h: usize, w: usize, end_turn: usize) -> Self {
+        assert!(0 < h && h <= H && 0 < w && w <= W);
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([seed; 32]);
+        let character = Coord::new(rng.gen_range(0..h) as isize, rng.gen_range(0..w) as isize);
+        let mut cells = [[Cell::Wall; W]; H];
+        for (y, row) in cells.iter_mut().enumerate().take(h) {
+            for (x, cell) in row.iter_mut().enumerate().take(w) {
+                *cell = Cell::Floor;
+                if y as isize == character.y && x as isize == character.x {
+                    continue;
+                }
+                *cell = Self::random_floor(&mut rng);
+            }
+        }
+        let mut state = Self::from_cells(cells, h, w, character);
+        state.end_turn = end_turn;
+        state
+    }
+
+    // 穴掘り法で作った、全ての床がつながった迷路の盤面
+    // y, xが共に偶数のマスを部屋として、隣の部屋との間の壁を掘りながら深さ優先でたどる
+    pub fn with_walls(seed: u8) -> Self {
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([seed; 32]);
+        let mut cells = [[Cell::Wall; W]; H];
+        let start = Coord::new(0, 0);
+        cells[0][0] = Cell::Floor;
+        let mut stack = vec![start];
+        while let Some(&now) = stack.last() {
+            let mut directions = [0, 1, 2, 3];
+            directions.shuffle(&mut rng);
+            let next = directions.iter().find_map(|&direction| {
+                let next = Coord::new(now.y + DY[direction] * 2, now.x + DX[direction] * 2);
+                let is_wall = Self::is_inside(next) && cells[next.y as usize][next.x as usize] == Cell::Wall;
+                is_wall.then_some((direction, next))
+            });
+            match next {
+                Some((direction, next)) => {
+                    cells[(now.y + DY[direction]) as usize][(now.x + DX[direction]) as usize] = Cell::Floor;
+                    cells[next.y as usize][next.x as usize] = Cell::Floor;
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        let floors: Vec<Coord> = (0..H)
+            .flat_map(|y| (0..W).map(move |x| Coord::new(y as isize, x as isize)))
+            .filter(|coord| cells[coord.y as usize][coord.x as usize] == Cell::Floor)
+            .collect();
+        let character = floors[rng.gen_range(0..floors.len())];
+        for coord in floors {
+            if coord != character {
+                cells[coord.y as usize][coord.x as usize] = Self::random_floor(&mut rng);
+            }
+        }
+        Self::from_cells(cells, H, W, character)
+    }
+
+    // ポイントが0なら何も無い床
+    fn random_floor(rng: &mut impl Rng) -> Cell {
+        match rng.gen_range(0..10) {
+            0 => Cell::Floor,
+            point => Cell::Point(point),
+        }
+    }
+
+    // 作ったマスから盤面を作る。h * wより外側は壁にしておく
+    // キャラクターに動けるマスが無ければpanicする
+    pub fn from_cells(cells: [[Cell; W]; H], h: usize, w: usize, character: Coord) -> Self {
+        Self::try_from_cells(cells, h, w, character).unwrap_or_else(|| {
+            panic!("the character at ({}, {}) has no cell to move to", character.y, character.x)
+        })
+    }
+
+    // from_cellsと同じだが、キャラクターに動けるマスが無ければNoneを返す
+    // 一度動ければ来た道を戻れるので、最初に動けるマスがあればゲームの最後まで動ける
+    pub fn try_from_cells(cells: [[Cell; W]; H], h: usize, w: usize, character: Coord) -> Option<Self> {
+        let state = Self {
+            cells,
+            h,
+            w,
+            turn: 0,
+            end_turn: END_TURN,
+            character,
+            game_score: 0,
+            evaluated_score: 0.0,
+            first_action: -1,
+        };
+        (!state.legal_actions().is_empty()).then_some(state)
+    }
+
+    fn is_inside(coord: Coord) -> bool {
+        coord.y >= 0 && coord.y < H as isize && coord.x >= 0 && coord.x < W as isize
+    }
+
+    // 盤面の中にあり、壁でなければ進める
+    pub fn is_passable(&self, coord: Coord) -> bool {
+        Self::is_inside(coord) && self.cells[coord.y as usize][coord.x as usize] != Cell::Wall
+    }
+
+    // ゲームの終了判定
+    pub fn is_done(&self) -> bool {
+        self.turn == self.end_turn
+    }
+
+    // 指定したactionでゲームを1ターン進める
+    pub fn advance(&mut self, action: usize) {
+        self.character.x += DX[action];
+        self.character.y += DY[action];
+        let cell = &mut self.cells[self.character.y as usize][self.character.x as usize];
+        if let Cell::Point(point) = *cell {
+            self.game_score += point;
+            *cell = Cell::Floor;
+        }
+        self.turn += 1;
+    }
+
+    // 現在の状況でプレイヤーが可能な行動を全て取得する
+    pub fn legal_actions(&self) -> Vec<usize> {
+        let mut actions = Vec::new();
+        for action in 0..4 {
+            let next = Coord::new(self.character.y + DY[action], self.character.x + DX[action]);
+            if self.is_passable(next) {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+}
+
+// 現在のゲーム状況を文字列にする
+impl fmt::Display for MazeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut ss = String::from("");
+
+        for h in 0..self.h {
+            for w in 0..self.w {
+                if self.character.y == h as isize && self.character.x == w as isize {
+                    ss += "@";
+                } else {
+                    match self.cells[h][w] {
+                        Cell::Wall => ss += "#",
+                        Cell::Point(point) => ss += &point.to_string(),
+                        Cell::Floor => ss += ".",
+                    }
+                }
+            }
+            ss += "\n";
+        }
+        ss += &format!("turn: {} score: {}", self.turn, self.game_score);
+        f.write_str(&ss)
+    }
+}
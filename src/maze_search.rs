@@ -0,0 +1,106 @@
+use std::collections::BinaryHeap;
+
+use crate::evaluator::Evaluator;
+use crate::maze::MazeState;
+
+// 1手先の評価が最も高い行動を選ぶ(同点なら先に見つけた行動)
+// 動けるマスが無い盤面はMazeState::from_cellsで作れないので、合法手は必ずある
+pub fn greedy_action(state: &MazeState, evaluator: &dyn Evaluator) -> usize {
+    let mut best: Option<(f64, usize)> = None;
+    for action in state.legal_actions() {
+        let mut now_state = *state;
+        now_state.advance(action);
+        let score = evaluator.evaluate(&now_state);
+        if best.is_none_or(|(best_score, _)| score > best_score) {
+            best = Some((score, action));
+        }
+    }
+    best.expect("the character has no legal action").1
+}
+
+pub fn beam_search_action(state: &MazeState, beam_width: usize, beam_depth: usize, evaluator: &dyn Evaluator) -> usize {
+    let mut now_beam: BinaryHeap<MazeState> = BinaryHeap::new();
+    now_beam.push(*state);
+    let mut best_state = *state;
+
+    for t in 0..beam_depth {
+        let mut next_beam: BinaryHeap<MazeState> = BinaryHeap::new();
+        for _ in 0..beam_width {
+            if now_beam.is_empty() {
+                break;
+            }
+            let now_state = now_beam.pop().unwrap();
+            let legal_actions = now_state.legal_actions();
+            for action in legal_actions {
+                let mut next_state = now_state;
+                next_state.advance(action);
+                next_state.evaluated_score = evaluator.evaluate(&next_state);
+                if t == 0 {
+                    next_state.first_action = action as isize;
+                }
+                next_beam.push(next_state);
+            }
+        }
+
+        now_beam = next_beam;
+        let Some(&next_best) = now_beam.peek() else {
+            break;
+        };
+        best_state = next_best;
+        if best_state.is_done() {
+            break;
+        }
+    }
+    usize::try_from(best_state.first_action).expect("the character has no legal action")
+}
+
+pub fn chokudai_search_action(
+    state: &MazeState,
+    beam_width: usize,
+    beam_depth: usize,
+    beam_number: usize,
+    evaluator: &dyn Evaluator,
+) -> usize {
+    let mut beam = vec![BinaryHeap::new(); beam_depth + 1];
+    beam[0].push(*state);
+
+    for _ in 0..beam_number {
+        for t in 0..beam_depth {
+            // 取り出した盤面は次の周回で選び直さないように、beam[t]から直接取り出す
+            let (now_beams, next_beams) = beam.split_at_mut(t + 1);
+            let now_beam = &mut now_beams[t];
+            let next_beam = &mut next_beams[0];
+
+            for _ in 0..beam_width {
+                if now_beam.is_empty() {
+                    break;
+                }
+
+                let now_state: MazeState = *now_beam.peek().unwrap();
+                if now_state.is_done() {
+                    break;
+                }
+                now_beam.pop();
+                let legal_actions = now_state.legal_actions();
+                for action in legal_actions {
+                    let mut next_state = now_state;
+                    next_state.advance(action);
+                    next_state.evaluated_score = evaluator.evaluate(&next_state);
+                    if t == 0 {
+                        next_state.first_action = action as isize;
+                    }
+                    next_beam.push(next_state);
+                }
+            }
+        }
+    }
+
+    for t in (1..=beam_depth).rev() {
+        let now_beam = &beam[t];
+        if !now_beam.is_empty() {
+            return now_beam.peek().unwrap().first_action as usize;
+        }
+    }
+
+    0
+}
@@ -0,0 +1,321 @@
+use rand::prelude::*;
+use std::fmt;
+
+use crate::budget::Budget;
+use crate::two_player::{TwoPlayerState, WinningStatus};
+
+// プレイアウトで手を選ぶ方法
+pub trait PlayoutPolicy<S> {
+    fn next_action(&self, state: &S, rng: &mut dyn RngCore) -> usize;
+}
+
+// 合法手から一様ランダムに選ぶ
+pub struct RandomPlayout;
+
+impl<S: TwoPlayerState> PlayoutPolicy<S> for RandomPlayout {
+    fn next_action(&self, state: &S, rng: &mut dyn RngCore) -> usize {
+        let legal_actions = state.legal_actions();
+        legal_actions[rng.gen_range(0..legal_actions.len())]
+    }
+}
+
+// epsilonの確率でランダム、それ以外は１手先の評価が最も良い手を選ぶ
+pub struct GreedyPlayout {
+    pub epsilon: f64,
+}
+
+impl<S: TwoPlayerState> PlayoutPolicy<S> for GreedyPlayout {
+    fn next_action(&self, state: &S, rng: &mut dyn RngCore) -> usize {
+        let legal_actions = state.legal_actions();
+        if rng.gen_range(0.0..1.0) < self.epsilon {
+            return legal_actions[rng.gen_range(0..legal_actions.len())];
+        }
+        // 進めた後は相手の手番なので、相手から見た評価が最も低い手を選ぶ
+        *legal_actions
+            .iter()
+            .min_by_key(|&&action| {
+                let mut next_state = state.clone();
+                next_state.advance(action);
+                next_state.get_score()
+            })
+            .unwrap()
+    }
+}
+
+// 手番のプレイヤーから見た勝敗を0.0~1.0の価値にする
+pub fn status_value(status: WinningStatus) -> f64 {
+    match status {
+        WinningStatus::Win => 1.0,
+        WinningStatus::Lose => 0.0,
+        _ => 0.5,
+    }
+}
+
+// 葉ノードの価値の求め方
+enum Leaf<S> {
+    // 終局までプレイアウトする(UCT)
+    Playout(Box<dyn PlayoutPolicy<S>>),
+    // 静的な評価(手番のプレイヤーから見た0.0~1.0の価値)をそのまま使う(Thunder)
+    Static(Box<dyn Fn(&S) -> f64>),
+}
+
+impl<S: TwoPlayerState> Leaf<S> {
+    fn value(&self, state: &S, rng: &mut dyn RngCore) -> f64 {
+        match self {
+            Leaf::Playout(playout) => playout_value(state, playout.as_ref(), rng),
+            Leaf::Static(evaluate) => evaluate(state),
+        }
+    }
+}
+
+// 手番のプレイヤーから見た評価をシグモイドで0.0~1.0にする
+pub fn sigmoid_evaluation<S: TwoPlayerState>(state: &S, scale: f64) -> f64 {
+    1.0 / (1.0 + (-(state.get_score() as f64) / scale).exp())
+}
+
+// thunder_actionで評価をシグモイドにかけるときの尺度(ポイント差)
+pub const THUNDER_SCALE: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MctsConfig {
+    pub c: f64,                  // UCB1の探索の強さ
+    pub expand_threshold: usize, // この回数訪れたノードを展開する
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            c: 1.0,
+            expand_threshold: 10,
+        }
+    }
+}
+
+struct Node<S> {
+    state: S,
+    w: f64, // このノードの手番のプレイヤーから見た価値の合計
+    n: usize,
+    children: Vec<(usize, Node<S>)>,
+}
+
+impl<S: TwoPlayerState> Node<S> {
+    fn new(state: S) -> Self {
+        Self {
+            state,
+            w: 0.0,
+            n: 0,
+            children: Vec::new(),
+        }
+    }
+
+    // 親(このノードに手を打ったプレイヤー)から見た勝率
+    fn parent_win_rate(&self) -> f64 {
+        if self.n == 0 {
+            0.5
+        } else {
+            1.0 - self.w / self.n as f64
+        }
+    }
+
+    fn expand(&mut self) {
+        self.children = self
+            .state
+            .legal_actions()
+            .into_iter()
+            .map(|action| {
+                let mut next_state = self.state.clone();
+                next_state.advance(action);
+                (action, Node::new(next_state))
+            })
+            .collect();
+    }
+
+    // まだ訪れていない子があればそれを、無ければUCB1が最大の子を選ぶ
+    fn select_child(&mut self, c: f64) -> &mut Node<S> {
+        let ln_n = (self.n as f64).ln();
+        let index = match self.children.iter().position(|(_, child)| child.n == 0) {
+            Some(index) => index,
+            None => {
+                let ucb1 = |child: &Node<S>| child.parent_win_rate() + c * (ln_n / child.n as f64).sqrt();
+                (0..self.children.len())
+                    .max_by(|&a, &b| ucb1(&self.children[a].1).total_cmp(&ucb1(&self.children[b].1)))
+                    .unwrap()
+            }
+        };
+        &mut self.children[index].1
+    }
+
+    // このノードの手番のプレイヤーから見た価値を返す
+    fn evaluate(&mut self, config: &MctsConfig, leaf: &Leaf<S>, rng: &mut dyn RngCore) -> f64 {
+        let value = if self.state.is_done() {
+            status_value(self.state.winning_status())
+        } else if self.children.is_empty() {
+            let value = leaf.value(&self.state, rng);
+            if self.n + 1 >= config.expand_threshold {
+                self.expand();
+            }
+            value
+        } else {
+            1.0 - self.select_child(config.c).evaluate(config, leaf, rng)
+        };
+        self.w += value;
+        self.n += 1;
+        value
+    }
+
+    fn count_nodes(&self) -> usize {
+        1 + self.children.iter().map(|(_, child)| child.count_nodes()).sum::<usize>()
+    }
+}
+
+// 終局までplayoutで打ち、stateの手番のプレイヤーから見た価値を返す
+fn playout_value<S: TwoPlayerState>(state: &S, playout: &dyn PlayoutPolicy<S>, rng: &mut dyn RngCore) -> f64 {
+    let mut now_state = state.clone();
+    let mut is_same_player = true;
+    while !now_state.is_done() {
+        let action = playout.next_action(&now_state, rng);
+        now_state.advance(action);
+        is_same_player = !is_same_player;
+    }
+    let value = status_value(now_state.winning_status());
+    if is_same_player {
+        value
+    } else {
+        1.0 - value
+    }
+}
+
+// ルートの子ごとの訪問回数と、ルートの手番のプレイヤーから見た勝率
+#[derive(Clone, Copy, Debug)]
+pub struct ChildStats {
+    pub action: usize,
+    pub visits: usize,
+    pub win_rate: f64,
+}
+
+// UCTによるモンテカルロ木探索
+// 手が進んだらadvanceで木をたどり、読んだ部分木を次の探索で使い回す
+pub struct Mcts<S> {
+    root: Node<S>,
+    pub config: MctsConfig,
+    leaf: Leaf<S>,
+}
+
+impl<S: TwoPlayerState> Mcts<S> {
+    pub fn new(state: &S, config: MctsConfig, playout: Box<dyn PlayoutPolicy<S>>) -> Self {
+        Self {
+            root: Node::new(state.clone()),
+            config,
+            leaf: Leaf::Playout(playout),
+        }
+    }
+
+    // Thunderサーチ: プレイアウトの代わりに葉ノードを静的に評価する
+    // 初めて訪れたノードはすぐ展開し、子は探索項の無い勝率だけで選ぶ
+    pub fn thunder(state: &S, evaluate: impl Fn(&S) -> f64 + 'static) -> Self {
+        Self {
+            root: Node::new(state.clone()),
+            config: MctsConfig {
+                c: 0.0,
+                expand_threshold: 1,
+            },
+            leaf: Leaf::Static(Box::new(evaluate)),
+        }
+    }
+
+    // 予算の分だけ探索し、最も多く訪れた手を返す
+    pub fn search(&mut self, budget: &Budget, rng: &mut impl Rng) -> usize {
+        assert!(!self.root.state.is_done(), "cannot choose an action in a finished game");
+        if self.root.children.is_empty() {
+            self.root.expand();
+        }
+        let mut timer = budget.start();
+        while timer.step() {
+            self.root.evaluate(&self.config, &self.leaf, rng);
+        }
+        self.best_action()
+    }
+
+    pub fn best_action(&self) -> usize {
+        self.root_stats().iter().max_by_key(|stats| stats.visits).unwrap().action
+    }
+
+    pub fn root_stats(&self) -> Vec<ChildStats> {
+        self.root
+            .children
+            .iter()
+            .map(|(action, child)| ChildStats {
+                action: *action,
+                visits: child.n,
+                win_rate: child.parent_win_rate(),
+            })
+            .collect()
+    }
+
+    // 木に含まれるノード数
+    pub fn tree_size(&self) -> usize {
+        self.root.count_nodes()
+    }
+
+    // actionを打った後の子をルートにする。展開されていなければ木を作り直す
+    pub fn advance(&mut self, action: usize) {
+        let children = std::mem::take(&mut self.root.children);
+        self.root = match children.into_iter().find(|(child_action, _)| *child_action == action) {
+            Some((_, child)) => child,
+            None => {
+                let mut state = self.root.state.clone();
+                state.advance(action);
+                Node::new(state)
+            }
+        };
+    }
+
+    // stateと同じ局面を子か孫から探してルートにする(自分と相手が１手ずつ打った後など)
+    // 見つからなければ木を作り直す
+    pub fn sync(&mut self, state: &S) {
+        let hash = state.hash();
+        if self.root.state.hash() == hash {
+            return;
+        }
+        let children = std::mem::take(&mut self.root.children);
+        let mut found = None;
+        for (_, child) in children {
+            if child.state.hash() == hash {
+                found = Some(child);
+                break;
+            }
+            if let Some((_, grandchild)) = child.children.into_iter().find(|(_, grandchild)| grandchild.state.hash() == hash) {
+                found = Some(grandchild);
+                break;
+            }
+        }
+        self.root = found.unwrap_or_else(|| Node::new(state.clone()));
+    }
+}
+
+// ルートの子ごとの訪問回数と勝率を表示する
+impl<S: TwoPlayerState> fmt::Display for Mcts<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "root visits: {}", self.root.n)?;
+        writeln!(f, "action\tvisits\twin rate")?;
+        for stats in self.root_stats() {
+            writeln!(f, "{}\t{}\t{:.3}", stats.action, stats.visits, stats.win_rate)?;
+        }
+        Ok(())
+    }
+}
+
+// 毎回新しい木でMCTSを行い、行動を返す
+pub fn mcts_action<S: TwoPlayerState>(
+    state: &S,
+    config: MctsConfig,
+    budget: &Budget,
+    rng: &mut impl Rng,
+) -> usize {
+    Mcts::new(state, config, Box::new(RandomPlayout)).search(budget, rng)
+}
+
+// 毎回新しい木でThunderサーチを行い、行動を返す
+pub fn thunder_action<S: TwoPlayerState>(state: &S, budget: &Budget, rng: &mut impl Rng) -> usize {
+    Mcts::thunder(state, |state: &S| sigmoid_evaluation(state, THUNDER_SCALE)).search(budget, rng)
+}
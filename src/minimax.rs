@@ -0,0 +1,112 @@
+use crate::two_player::TwoPlayerState;
+
+// 探索で選んだ行動と、その行動から始まる最善応手手順(読み筋)
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub action: usize,
+    pub value: isize, // 探索開始時の手番のプレイヤーから見た評価
+    pub principal_variation: Vec<usize>,
+    pub nodes: usize, // 探索したノード数
+    pub depth: usize, // 読み切った深さ
+}
+
+// 手番のプレイヤーから見た評価をそのまま使う評価関数
+pub fn score_evaluation<S: TwoPlayerState>(state: &S) -> isize {
+    state.get_score()
+}
+
+// is_maximizing: 探索開始時の手番のプレイヤーの番ならtrue
+// 評価は常に探索開始時の手番のプレイヤーから見た値で返す
+fn minimax_score<S: TwoPlayerState>(
+    state: &S,
+    depth: usize,
+    is_maximizing: bool,
+    evaluate: &impl Fn(&S) -> isize,
+    nodes: &mut usize,
+) -> (isize, Vec<usize>) {
+    *nodes += 1;
+    if state.is_done() || depth == 0 {
+        let value = evaluate(state);
+        return (if is_maximizing { value } else { -value }, Vec::new());
+    }
+
+    let mut best: Option<(isize, Vec<usize>)> = None;
+    for action in state.legal_actions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let (value, mut line) = minimax_score(&next_state, depth - 1, !is_maximizing, evaluate, nodes);
+        let is_better = match &best {
+            None => true,
+            Some((best_value, _)) => {
+                if is_maximizing {
+                    value > *best_value
+                } else {
+                    value < *best_value
+                }
+            }
+        };
+        if is_better {
+            line.insert(0, action);
+            best = Some((value, line));
+        }
+    }
+    best.unwrap()
+}
+
+// depth手先までミニマックス法で読み、最善の行動を返す
+pub fn minimax_action<S: TwoPlayerState>(state: &S, depth: usize, evaluate: &impl Fn(&S) -> isize) -> SearchResult {
+    assert!(depth > 0);
+    assert!(!state.is_done(), "cannot choose an action in a finished game");
+    let mut nodes = 0;
+    let (value, principal_variation) = minimax_score(state, depth, true, evaluate, &mut nodes);
+    SearchResult {
+        action: principal_variation[0],
+        value,
+        principal_variation,
+        nodes,
+        depth,
+    }
+}
+
+// 評価は常に手番のプレイヤーから見た値で返す
+fn negamax_score<S: TwoPlayerState>(
+    state: &S,
+    depth: usize,
+    evaluate: &impl Fn(&S) -> isize,
+    nodes: &mut usize,
+) -> (isize, Vec<usize>) {
+    *nodes += 1;
+    if state.is_done() || depth == 0 {
+        return (evaluate(state), Vec::new());
+    }
+
+    let mut best_value = isize::MIN;
+    let mut best_line = Vec::new();
+    for action in state.legal_actions() {
+        let mut next_state = state.clone();
+        next_state.advance(action);
+        let (value, mut line) = negamax_score(&next_state, depth - 1, evaluate, nodes);
+        if -value > best_value {
+            best_value = -value;
+            line.insert(0, action);
+            best_line = line;
+        }
+    }
+    (best_value, best_line)
+}
+
+// depth手先までネガマックス法で読み、最善の行動を返す
+// ミニマックス法と同じ行動を選ぶが、手番ごとに符号を反転させるだけで書ける
+pub fn negamax_action<S: TwoPlayerState>(state: &S, depth: usize, evaluate: &impl Fn(&S) -> isize) -> SearchResult {
+    assert!(depth > 0);
+    assert!(!state.is_done(), "cannot choose an action in a finished game");
+    let mut nodes = 0;
+    let (value, principal_variation) = negamax_score(state, depth, evaluate, &mut nodes);
+    SearchResult {
+        action: principal_variation[0],
+        value,
+        principal_variation,
+        nodes,
+        depth,
+    }
+}
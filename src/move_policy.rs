@@ -0,0 +1,182 @@
+use std::collections::VecDeque;
+
+use crate::auto_move_maze::{AutoMoveMazeState, H, W};
+use crate::coord::{Coord, DX, DY};
+
+// 自動で動くキャラクターの動き方
+// 同じ状態からは必ず同じ方向を返すこと(配置のスコアが一意に決まるようにするため)
+pub trait MovePolicy {
+    // character_id番目のキャラクターが次に進む方向(DX, DYの添字)を返す
+    fn next_action(&self, state: &AutoMoveMazeState, character_id: usize) -> usize;
+}
+
+fn is_inside(y: isize, x: isize) -> bool {
+    y >= 0 && y < H as isize && x >= 0 && x < W as isize
+}
+
+fn legal_actions(coord: &Coord) -> Vec<usize> {
+    (0..4).filter(|&action| is_inside(coord.y + DY[action], coord.x + DX[action])).collect()
+}
+
+// seedとターン、キャラクターから決まる擬似乱数(splitmix64)
+fn hash(seed: u64, turn: usize, character_id: usize) -> u64 {
+    let mut z = seed ^ (turn as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ (character_id as u64).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// 評価が同点の方向が複数あるときの選び方
+#[derive(Clone, Copy, Debug, Default)]
+pub enum TieBreak {
+    // DX, DYの順で最初のもの
+    #[default]
+    First,
+    // DX, DYの順で最後のもの
+    Last,
+    // seedから決まる擬似乱数で選ぶ
+    Hashed(u64),
+}
+
+impl TieBreak {
+    fn pick(&self, candidates: &[usize], state: &AutoMoveMazeState, character_id: usize) -> usize {
+        match self {
+            TieBreak::First => candidates[0],
+            TieBreak::Last => candidates[candidates.len() - 1],
+            TieBreak::Hashed(seed) => {
+                candidates[(hash(*seed, state.turn, character_id) % candidates.len() as u64) as usize]
+            }
+        }
+    }
+}
+
+// 評価値が最大の方向を全て集め、tie_breakで１つ選ぶ
+fn pick_best(
+    scored: impl Iterator<Item = (usize, usize)>,
+    tie_break: TieBreak,
+    state: &AutoMoveMazeState,
+    character_id: usize,
+) -> usize {
+    let mut best_score = 0;
+    let mut candidates = Vec::new();
+    for (action, score) in scored {
+        if candidates.is_empty() || score > best_score {
+            best_score = score;
+            candidates.clear();
+            candidates.push(action);
+        } else if score == best_score {
+            candidates.push(action);
+        }
+    }
+    tie_break.pick(&candidates, state, character_id)
+}
+
+// 隣のマスのうち一番ポイントが高いマスへ進む
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Greedy {
+    pub tie_break: TieBreak,
+}
+
+impl MovePolicy for Greedy {
+    fn next_action(&self, state: &AutoMoveMazeState, character_id: usize) -> usize {
+        let character = &state.characters[character_id];
+        let scored = legal_actions(character).into_iter().map(|action| {
+            let ty = (character.y + DY[action]) as usize;
+            let tx = (character.x + DX[action]) as usize;
+            (action, state.points[ty][tx])
+        });
+        pick_best(scored, self.tie_break, state, character_id)
+    }
+}
+
+// depthマス先まで全ての道順を調べ、取れるポイントの合計が最大になる道順の１歩目へ進む
+// 他のキャラクターの動きは考えない
+#[derive(Clone, Copy, Debug)]
+pub struct Lookahead {
+    pub depth: usize,
+    pub tie_break: TieBreak,
+}
+
+impl Lookahead {
+    fn best_sum(&self, state: &AutoMoveMazeState, coord: Coord, depth: usize, path: &mut Vec<Coord>) -> usize {
+        if depth == 0 {
+            return 0;
+        }
+        let mut best = 0;
+        for action in legal_actions(&coord) {
+            let next = Coord::new(coord.y + DY[action], coord.x + DX[action]);
+            let point = if path.contains(&next) {
+                0
+            } else {
+                state.points[next.y as usize][next.x as usize]
+            };
+            path.push(next);
+            best = best.max(point + self.best_sum(state, next, depth - 1, path));
+            path.pop();
+        }
+        best
+    }
+}
+
+impl MovePolicy for Lookahead {
+    fn next_action(&self, state: &AutoMoveMazeState, character_id: usize) -> usize {
+        let character = state.characters[character_id];
+        let mut path = Vec::with_capacity(self.depth);
+        let scored = legal_actions(&character).into_iter().map(|action| {
+            let next = Coord::new(character.y + DY[action], character.x + DX[action]);
+            path.push(next);
+            let sum = state.points[next.y as usize][next.x as usize] + self.best_sum(state, next, self.depth.saturating_sub(1), &mut path);
+            path.pop();
+            (action, sum)
+        });
+        pick_best(scored, self.tie_break, state, character_id)
+    }
+}
+
+// 幅優先探索で一番近いポイントのあるマスを探し、そこへ向かう
+// 同じ距離ならDX, DYの順で先に見つかったマスを選ぶ。ポイントが残っていなければGreedyと同じ
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NearestPoint;
+
+impl MovePolicy for NearestPoint {
+    fn next_action(&self, state: &AutoMoveMazeState, character_id: usize) -> usize {
+        let start = state.characters[character_id];
+        // first_actions[y][x]: startからそのマスへ最短で行くときの１歩目
+        let mut first_actions = [[None; W]; H];
+        let mut queue = VecDeque::new();
+        for action in legal_actions(&start) {
+            let next = Coord::new(start.y + DY[action], start.x + DX[action]);
+            if first_actions[next.y as usize][next.x as usize].is_none() {
+                first_actions[next.y as usize][next.x as usize] = Some(action);
+                queue.push_back(next);
+            }
+        }
+        while let Some(coord) = queue.pop_front() {
+            let first_action = first_actions[coord.y as usize][coord.x as usize];
+            if state.points[coord.y as usize][coord.x as usize] > 0 {
+                return first_action.unwrap();
+            }
+            for action in legal_actions(&coord) {
+                let next = Coord::new(coord.y + DY[action], coord.x + DX[action]);
+                if next != start && first_actions[next.y as usize][next.x as usize].is_none() {
+                    first_actions[next.y as usize][next.x as usize] = first_action;
+                    queue.push_back(next);
+                }
+            }
+        }
+        Greedy::default().next_action(state, character_id)
+    }
+}
+
+// 盤面の内側の方向から、seedで決まる擬似乱数で１つ選ぶ
+#[derive(Clone, Copy, Debug)]
+pub struct RandomWalk {
+    pub seed: u64,
+}
+
+impl MovePolicy for RandomWalk {
+    fn next_action(&self, state: &AutoMoveMazeState, character_id: usize) -> usize {
+        let actions = legal_actions(&state.characters[character_id]);
+        actions[(hash(self.seed, state.turn, character_id) % actions.len() as u64) as usize]
+    }
+}
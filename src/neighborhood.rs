@@ -0,0 +1,256 @@
+use rand::prelude::*;
+
+use crate::auto_move_maze::{AutoMoveMazeState, H, W};
+use crate::coord::{Coord, DX, DY};
+
+// 配置を少しだけ変える近傍操作
+pub trait Operator {
+    fn name(&self) -> &'static str;
+    fn apply(&self, state: &mut AutoMoveMazeState, rng: &mut dyn RngCore);
+}
+
+fn clamp_to_board(y: isize, x: isize) -> Coord {
+    Coord::new(y.clamp(0, H as isize - 1), x.clamp(0, W as isize - 1))
+}
+
+// ランダムに１匹を好きなマスへ飛ばす
+pub struct Teleport;
+
+impl Operator for Teleport {
+    fn name(&self) -> &'static str {
+        "teleport"
+    }
+
+    fn apply(&self, state: &mut AutoMoveMazeState, mut rng: &mut dyn RngCore) {
+        state.transition(&mut rng);
+    }
+}
+
+// ランダムに１匹を上下左右いずれかへ1~max_stepマスずらす
+pub struct Shift {
+    max_step: usize,
+}
+
+impl Shift {
+    pub fn new(max_step: usize) -> Self {
+        assert!(max_step >= 1, "max_step must be at least 1");
+        Self { max_step }
+    }
+}
+
+impl Operator for Shift {
+    fn name(&self) -> &'static str {
+        "shift"
+    }
+
+    fn apply(&self, state: &mut AutoMoveMazeState, rng: &mut dyn RngCore) {
+        let character_id = rng.gen_range(0..state.characters.len());
+        let character = &mut state.characters[character_id];
+        let direction = rng.gen_range(0..4);
+        let step = rng.gen_range(1..=self.max_step) as isize;
+        *character = clamp_to_board(character.y + DY[direction] * step, character.x + DX[direction] * step);
+    }
+}
+
+// ランダムな２匹の位置を入れ替える
+// キャラクターは区別されず、移動中にポイントも変わらないので、Greedyなどではスコアが変わらない
+// RandomWalkやTieBreak::Hashedのようにcharacter_idで動きが変わる方針のときだけ意味がある
+pub struct Swap;
+
+impl Operator for Swap {
+    fn name(&self) -> &'static str {
+        "swap"
+    }
+
+    fn apply(&self, state: &mut AutoMoveMazeState, rng: &mut dyn RngCore) {
+        let character_n = state.characters.len();
+        if character_n < 2 {
+            return;
+        }
+        let first = rng.gen_range(0..character_n);
+        let second = (first + rng.gen_range(1..character_n)) % character_n;
+        state.characters.swap(first, second);
+    }
+}
+
+// ランダムな２匹を同時に好きなマスへ飛ばす
+pub struct RelocateTwo;
+
+impl Operator for RelocateTwo {
+    fn name(&self) -> &'static str {
+        "relocate_two"
+    }
+
+    fn apply(&self, state: &mut AutoMoveMazeState, rng: &mut dyn RngCore) {
+        let character_n = state.characters.len();
+        let first = rng.gen_range(0..character_n);
+        let ids = if character_n < 2 {
+            vec![first]
+        } else {
+            vec![first, (first + rng.gen_range(1..character_n)) % character_n]
+        };
+        for id in ids {
+            state.characters[id] = Coord::new(rng.gen_range(0..H) as isize, rng.gen_range(0..W) as isize);
+        }
+    }
+}
+
+// 最後まで誰にも踏まれないポイントが最も多い区画へ１匹を近づける
+// 既にその区画にいるキャラクターは区画内の別のマスへ飛ばす
+pub struct TowardRichRegion {
+    block_size: usize,
+    max_step: usize,
+}
+
+impl TowardRichRegion {
+    pub fn new(block_size: usize, max_step: usize) -> Self {
+        assert!(block_size >= 1, "block_size must be at least 1");
+        assert!(max_step >= 1, "max_step must be at least 1");
+        Self { block_size, max_step }
+    }
+
+    fn richest_block(&self, state: &AutoMoveMazeState) -> (usize, usize) {
+        let remaining = state.remaining_points();
+        let block_h = H.div_ceil(self.block_size);
+        let block_w = W.div_ceil(self.block_size);
+        let mut block_sums = vec![vec![0; block_w]; block_h];
+        for (y, row) in remaining.iter().enumerate() {
+            for (x, point) in row.iter().enumerate() {
+                block_sums[y / self.block_size][x / self.block_size] += point;
+            }
+        }
+
+        let mut best = (0, 0);
+        for (by, row) in block_sums.iter().enumerate() {
+            for (bx, sum) in row.iter().enumerate() {
+                if *sum > block_sums[best.0][best.1] {
+                    best = (by, bx);
+                }
+            }
+        }
+        best
+    }
+}
+
+impl Operator for TowardRichRegion {
+    fn name(&self) -> &'static str {
+        "toward_rich_region"
+    }
+
+    fn apply(&self, state: &mut AutoMoveMazeState, rng: &mut dyn RngCore) {
+        let (by, bx) = self.richest_block(state);
+        let top = by * self.block_size;
+        let left = bx * self.block_size;
+        let bottom = (top + self.block_size).min(H);
+        let right = (left + self.block_size).min(W);
+
+        let character_id = rng.gen_range(0..state.characters.len());
+        let character = &mut state.characters[character_id];
+        let inside = (top..bottom).contains(&(character.y as usize)) && (left..right).contains(&(character.x as usize));
+        if inside {
+            *character = Coord::new(rng.gen_range(top..bottom) as isize, rng.gen_range(left..right) as isize);
+            return;
+        }
+
+        let target = Coord::new(((top + bottom) / 2) as isize, ((left + right) / 2) as isize);
+        for _ in 0..rng.gen_range(1..=self.max_step) {
+            if character.y != target.y {
+                character.y += (target.y - character.y).signum();
+            } else if character.x != target.x {
+                character.x += (target.x - character.x).signum();
+            }
+        }
+    }
+}
+
+// 標準で使う近傍操作一式
+// Swapはcharacter_idで動きが変わる方針でしか効かないので、必要なときに足す
+pub fn default_operators() -> Vec<Box<dyn Operator>> {
+    vec![
+        Box::new(Teleport),
+        Box::new(Shift::new(3)),
+        Box::new(RelocateTwo),
+        Box::new(TowardRichRegion::new(5, 3)),
+    ]
+}
+
+// 近傍操作の選び方
+pub trait Neighborhood {
+    // stateに近傍操作を１回適用し、使った操作の番号を返す
+    fn apply(&mut self, state: &mut AutoMoveMazeState, rng: &mut dyn RngCore) -> usize;
+
+    // 操作した結果が採用(改善)されたかを伝える
+    fn feedback(&mut self, _operator_id: usize, _improved: bool) {}
+}
+
+// 操作を一様ランダムに選ぶ
+pub struct UniformNeighborhood {
+    operators: Vec<Box<dyn Operator>>,
+}
+
+impl UniformNeighborhood {
+    pub fn new(operators: Vec<Box<dyn Operator>>) -> Self {
+        assert!(!operators.is_empty());
+        Self { operators }
+    }
+}
+
+impl Neighborhood for UniformNeighborhood {
+    fn apply(&mut self, state: &mut AutoMoveMazeState, rng: &mut dyn RngCore) -> usize {
+        let operator_id = rng.gen_range(0..self.operators.len());
+        self.operators[operator_id].apply(state, rng);
+        operator_id
+    }
+}
+
+// 最近の成功率が高い操作ほど選ばれやすくする
+// 成功率は指数移動平均で持ち、min_rateで全ての操作が時々は選ばれるようにする
+pub struct AdaptiveNeighborhood {
+    operators: Vec<Box<dyn Operator>>,
+    success_rates: Vec<f64>,
+    pub decay: f64,
+    pub min_rate: f64,
+}
+
+impl AdaptiveNeighborhood {
+    pub fn new(operators: Vec<Box<dyn Operator>>) -> Self {
+        assert!(!operators.is_empty());
+        let success_rates = vec![1.0; operators.len()];
+        Self {
+            operators,
+            success_rates,
+            decay: 0.99,
+            min_rate: 0.01,
+        }
+    }
+
+    pub fn success_rates(&self) -> Vec<(&'static str, f64)> {
+        self.operators
+            .iter()
+            .zip(&self.success_rates)
+            .map(|(operator, rate)| (operator.name(), *rate))
+            .collect()
+    }
+}
+
+impl Neighborhood for AdaptiveNeighborhood {
+    fn apply(&mut self, state: &mut AutoMoveMazeState, rng: &mut dyn RngCore) -> usize {
+        let total: f64 = self.success_rates.iter().map(|rate| rate + self.min_rate).sum();
+        let mut ticket = rng.gen_range(0.0..total);
+        let mut operator_id = self.operators.len() - 1;
+        for (id, rate) in self.success_rates.iter().enumerate() {
+            ticket -= rate + self.min_rate;
+            if ticket < 0.0 {
+                operator_id = id;
+                break;
+            }
+        }
+        self.operators[operator_id].apply(state, rng);
+        operator_id
+    }
+
+    fn feedback(&mut self, operator_id: usize, improved: bool) {
+        let rate = &mut self.success_rates[operator_id];
+        *rate = self.decay * *rate + (1.0 - self.decay) * if improved { 1.0 } else { 0.0 };
+    }
+}
@@ -0,0 +1,211 @@
+use rand::prelude::*;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::budget::Budget;
+use crate::coord::{Coord, DX, DY};
+use crate::distance::DistanceMap;
+use crate::maze::{Cell, MazeState};
+
+// 盤面を表に引くときの特徴
+// 上下左右のマスの中身(壁なら4、それ以外はポイントを0, 1~3, 4~6, 7~9の4段階にしたもの)と、
+// 最も近いポイントへ向かう方向(無ければ4)
+fn state_features(state: &MazeState) -> [usize; 5] {
+    let mut features = [0; 5];
+    for action in 0..4 {
+        let next = Coord::new(state.character.y + DY[action], state.character.x + DX[action]);
+        features[action] = if state.is_passable(next) {
+            state.cells[next.y as usize][next.x as usize].point().div_ceil(3)
+        } else {
+            4
+        };
+    }
+    let points: Vec<Coord> = (0..state.h)
+        .flat_map(|y| (0..state.w).map(move |x| Coord::new(y as isize, x as isize)))
+        .filter(|coord| matches!(state.cells[coord.y as usize][coord.x as usize], Cell::Point(_)))
+        .collect();
+    let map = DistanceMap::from_sources(state, &points);
+    features[4] = state
+        .legal_actions()
+        .into_iter()
+        .filter_map(|action| {
+            let next = Coord::new(state.character.y + DY[action], state.character.x + DX[action]);
+            map.get(next).map(|distance| (distance, action))
+        })
+        .min()
+        .map_or(4, |(_, action)| action);
+    features
+}
+
+// 特徴をまとめてハッシュする(splitmix64)
+fn hash_features(features: &[usize]) -> u64 {
+    let mut hash = 0u64;
+    for &feature in features {
+        let mut z = hash ^ (feature as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        hash = z ^ (z >> 31);
+    }
+    hash
+}
+
+// QTableの大きさの上限(2^MAX_SIZE_LOG2個、1個32バイトなので32MB)。読み込むファイルのヘッダもこれで確かめる
+pub const MAX_SIZE_LOG2: usize = 20;
+
+// 特徴のハッシュを添字にした行動価値の表
+// 違う特徴が同じ場所に来ることもあるが、区別はしない
+pub struct QTable {
+    size_log2: usize,
+    values: Vec<[f64; 4]>,
+}
+
+impl QTable {
+    // 2^size_log2個のエントリを持つ
+    pub fn new(size_log2: usize) -> Self {
+        assert!(size_log2 <= MAX_SIZE_LOG2, "size_log2 must be at most {}", MAX_SIZE_LOG2);
+        Self {
+            size_log2,
+            values: vec![[0.0; 4]; 1 << size_log2],
+        }
+    }
+
+    fn index(&self, state: &MazeState) -> usize {
+        hash_features(&state_features(state)) as usize & ((1 << self.size_log2) - 1)
+    }
+
+    // 合法手の中で行動価値が最も高い行動
+    pub fn best_action(&self, state: &MazeState) -> usize {
+        let values = &self.values[self.index(state)];
+        state
+            .legal_actions()
+            .into_iter()
+            .max_by(|&a, &b| values[a].total_cmp(&values[b]))
+            .unwrap()
+    }
+
+    // 1行目に"qtable size_log2"、以降は0でないエントリを"添字 q0 q1 q2 q3"で書く
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "qtable {}", self.size_log2)?;
+        for (index, values) in self.values.iter().enumerate() {
+            if values.iter().any(|&value| value != 0.0) {
+                writeln!(writer, "{} {} {} {} {}", index, values[0], values[1], values[2], values[3])?;
+            }
+        }
+        writer.flush()
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = BufReader::new(File::open(path)?).lines();
+        let header = lines.next().ok_or_else(|| invalid("empty file".to_string()))??;
+        let size_log2: usize = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            ["qtable", size_log2] => size_log2.parse().map_err(|_| invalid(format!("bad header: {}", header)))?,
+            _ => return Err(invalid(format!("bad header: {}", header))),
+        };
+        if size_log2 > MAX_SIZE_LOG2 {
+            return Err(invalid(format!(
+                "table size 2^{} is larger than the limit 2^{}",
+                size_log2, MAX_SIZE_LOG2
+            )));
+        }
+        let mut table = Self::new(size_log2);
+        for (line_number, line) in lines.enumerate() {
+            let line = line?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let parsed = (fields.len() == 5)
+                .then(|| {
+                    let index: usize = fields[0].parse().ok()?;
+                    let mut values = [0.0; 4];
+                    for (value, field) in values.iter_mut().zip(&fields[1..]) {
+                        *value = field.parse().ok()?;
+                    }
+                    (index < table.values.len()).then_some((index, values))
+                })
+                .flatten();
+            let (index, values) = parsed.ok_or_else(|| invalid(format!("line {}: {}", line_number + 2, line)))?;
+            table.values[index] = values;
+        }
+        Ok(table)
+    }
+}
+
+// 学習の進み具合(0~1)に応じた値の変え方
+#[derive(Clone, Copy, Debug)]
+pub enum Schedule {
+    Constant(f64),
+    Linear { start: f64, end: f64 },      // startからendへ直線的に変える
+    Exponential { start: f64, end: f64 }, // 毎回一定の割合で変える。start, endは正
+}
+
+impl Schedule {
+    pub fn value(&self, progress: f64) -> f64 {
+        match *self {
+            Schedule::Constant(value) => value,
+            Schedule::Linear { start, end } => start + (end - start) * progress,
+            Schedule::Exponential { start, end } => start * (end / start).powf(progress),
+        }
+    }
+}
+
+// 学習の設定。それぞれ学習の進み具合(エピソード数/全エピソード数)で値を変える
+#[derive(Clone, Copy, Debug)]
+pub struct QLearningConfig {
+    pub epsilon: Schedule, // ランダムに動く確率
+    pub alpha: Schedule,   // 学習率
+    pub gamma: Schedule,   // 割引率
+}
+
+// new_stateで作った盤面でbudgetの回数だけエピソードを行い、tableを学習する
+// 報酬は1ターンに得たポイント。各エピソードの最終スコアを返す
+pub fn train(
+    table: &mut QTable,
+    new_state: impl Fn(&mut StdRng) -> MazeState,
+    budget: &Budget,
+    config: &QLearningConfig,
+    rng: &mut StdRng,
+) -> Vec<usize> {
+    let mut scores = Vec::new();
+    let mut timer = budget.start();
+    while timer.step() {
+        let progress = timer.progress().overall();
+        let epsilon = config.epsilon.value(progress);
+        let alpha = config.alpha.value(progress);
+        let gamma = config.gamma.value(progress);
+        let mut state = new_state(rng);
+        let mut index = table.index(&state);
+        while !state.is_done() {
+            let action = if rng.gen_range(0.0..1.0) < epsilon {
+                let legal_actions = state.legal_actions();
+                legal_actions[rng.gen_range(0..legal_actions.len())]
+            } else {
+                table.best_action(&state)
+            };
+            let score = state.game_score;
+            state.advance(action);
+            let reward = (state.game_score - score) as f64;
+            let next_index = table.index(&state);
+            let next_value = if state.is_done() {
+                0.0
+            } else {
+                let values = &table.values[next_index];
+                state
+                    .legal_actions()
+                    .into_iter()
+                    .map(|action| values[action])
+                    .fold(f64::MIN, f64::max)
+            };
+            let value = &mut table.values[index][action];
+            *value += alpha * (reward + gamma * next_value - *value);
+            index = next_index;
+        }
+        scores.push(state.game_score);
+    }
+    scores
+}
+
+// 学習した表で最も価値の高い行動を選ぶ
+pub fn q_learning_action(state: &MazeState, table: &QTable) -> usize {
+    table.best_action(state)
+}
@@ -0,0 +1,148 @@
+use rand::prelude::*;
+use std::fmt;
+
+use crate::coord::{Coord, DX, DY};
+use crate::two_player::{SimultaneousState, WinningStatus};
+
+pub const H: usize = 5;
+pub const W: usize = 5;
+pub const END_TURN: usize = 20;
+
+// 二人同時手番ゲームの例
+// 二人のプレイヤーが同時に上下左右四方向のいずれかに1マスずつ進む。
+// 床にあるポイントを踏むと自身のスコアとなり、床のポイントが消える。
+// 二人が同じターンに同じマスを踏んだときは、二人ともそのポイントを得る。
+// END_TURNの時点で相手よりスコアが高ければ勝ち
+#[derive(Clone, Debug)]
+pub struct SimultaneousMazeState {
+    pub h: usize,
+    pub w: usize,
+    pub end_turn: usize,
+    pub points: Vec<Vec<usize>>, // 床のポイントを1~9で表現する
+    pub turn: usize,
+    pub characters: [Coord; 2],
+    pub game_scores: [usize; 2],
+}
+
+impl SimultaneousMazeState {
+    pub fn new(seed: u64) -> Self {
+        Self::with_size(seed, H, W, END_TURN)
+    }
+
+    // 盤面の大きさとターン数を指定して作る。二人は中央の行の左右に置くので、wは3以上
+    pub fn with_size(seed: u64, h: usize, w: usize, end_turn: usize) -> Self {
+        assert!(h >= 1 && w >= 3);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let characters = [
+            Coord::new((h / 2) as isize, (w / 2) as isize - 1),
+            Coord::new((h / 2) as isize, (w / 2) as isize + 1),
+        ];
+        let mut points = vec![vec![0; w]; h];
+        for (y, row) in points.iter_mut().enumerate() {
+            for (x, point) in row.iter_mut().enumerate() {
+                if !characters.contains(&Coord::new(y as isize, x as isize)) {
+                    *point = rng.gen_range(0..10);
+                }
+            }
+        }
+        Self {
+            h,
+            w,
+            end_turn,
+            points,
+            turn: 0,
+            characters,
+            game_scores: [0, 0],
+        }
+    }
+
+    // ゲームの終了判定
+    pub fn is_done(&self) -> bool {
+        self.turn == self.end_turn
+    }
+
+    // 二人の行動で1ターン進める。先に二人とも動かしてからポイントを取る
+    pub fn advance(&mut self, actions: [usize; 2]) {
+        for (character, action) in self.characters.iter_mut().zip(actions) {
+            character.x += DX[action];
+            character.y += DY[action];
+        }
+        for (player, character) in self.characters.iter().enumerate() {
+            self.game_scores[player] += self.points[character.y as usize][character.x as usize];
+        }
+        for character in self.characters {
+            self.points[character.y as usize][character.x as usize] = 0;
+        }
+        self.turn += 1;
+    }
+
+    // playerが可能な行動を全て取得する
+    pub fn legal_actions(&self, player: usize) -> Vec<usize> {
+        let character = self.characters[player];
+        (0..4)
+            .filter(|&action| {
+                let ty = character.y + DY[action];
+                let tx = character.x + DX[action];
+                ty >= 0 && ty < self.h as isize && tx >= 0 && tx < self.w as isize
+            })
+            .collect()
+    }
+
+    // playerから見た勝敗
+    pub fn winning_status(&self, player: usize) -> WinningStatus {
+        if !self.is_done() {
+            return WinningStatus::Playing;
+        }
+        let (me, opponent) = (self.game_scores[player], self.game_scores[player ^ 1]);
+        if me > opponent {
+            WinningStatus::Win
+        } else if me < opponent {
+            WinningStatus::Lose
+        } else {
+            WinningStatus::Draw
+        }
+    }
+}
+
+impl SimultaneousState for SimultaneousMazeState {
+    fn legal_actions(&self, player: usize) -> Vec<usize> {
+        SimultaneousMazeState::legal_actions(self, player)
+    }
+
+    fn advance(&mut self, actions: [usize; 2]) {
+        SimultaneousMazeState::advance(self, actions)
+    }
+
+    fn is_done(&self) -> bool {
+        SimultaneousMazeState::is_done(self)
+    }
+
+    fn winning_status(&self, player: usize) -> WinningStatus {
+        SimultaneousMazeState::winning_status(self, player)
+    }
+}
+
+// 現在のゲーム状況を文字列にする。プレイヤー0をA、1をBで表し、同じマスにいればCとする
+impl fmt::Display for SimultaneousMazeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "turn:\t{}", self.turn)?;
+        for (player, name) in ["A", "B"].iter().enumerate() {
+            let character = self.characters[player];
+            writeln!(f, "score({}):\t{}\ty: {} x: {}", name, self.game_scores[player], character.y, character.x)?;
+        }
+        for h in 0..self.h {
+            for w in 0..self.w {
+                let here = Coord::new(h as isize, w as isize);
+                match (self.characters[0] == here, self.characters[1] == here) {
+                    (true, true) => write!(f, "C")?,
+                    (true, false) => write!(f, "A")?,
+                    (false, true) => write!(f, "B")?,
+                    _ if self.points[h][w] > 0 => write!(f, "{}", self.points[h][w])?,
+                    _ => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::arena::{play_match, MatchResult, StringAIPair};
+
+// 結果ファイルの1行: name opponent wins losses draws time_ns(自分) time_ns(相手) moves(自分) moves(相手) をタブ区切りで並べる
+fn to_line(result: &MatchResult) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        result.name,
+        result.opponent,
+        result.wins,
+        result.losses,
+        result.draws,
+        result.times[0].as_nanos(),
+        result.times[1].as_nanos(),
+        result.moves[0],
+        result.moves[1]
+    )
+}
+
+fn parse_line(line: &str) -> Option<MatchResult> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() != 9 {
+        return None;
+    }
+    let number = |i: usize| fields[i].parse::<u64>().ok();
+    Some(MatchResult {
+        name: fields[0].to_string(),
+        opponent: fields[1].to_string(),
+        wins: number(2)? as usize,
+        losses: number(3)? as usize,
+        draws: number(4)? as usize,
+        times: [Duration::from_nanos(number(5)?), Duration::from_nanos(number(6)?)],
+        moves: [number(7)? as usize, number(8)? as usize],
+    })
+}
+
+// 結果ファイルを読む。ファイルが無ければ空、読めない行は飛ばす
+pub fn load_results(path: &Path) -> io::Result<Vec<MatchResult>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut results = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Some(result) = parse_line(&line?) {
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+// 総当たり戦。各組み合わせをseed_number個の盤面で先後入れ替えて打つ
+// results_pathを渡すと、終わった組み合わせを1つずつ追記し、次に呼んだときはそこから再開する
+// 結果ファイルはタブ区切りなので、そのときAIの名前にタブや改行があればエラーにする
+// on_resultは新しく打った組み合わせが終わるたびに呼ぶ(進み具合の表示などに使う)
+pub fn round_robin(
+    ais: &[StringAIPair],
+    seed_number: usize,
+    results_path: Option<&Path>,
+    mut on_result: impl FnMut(&MatchResult),
+) -> io::Result<Vec<MatchResult>> {
+    if results_path.is_some() {
+        if let Some(ai) = ais.iter().find(|ai| ai.name.contains(['\t', '\n', '\r'])) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("AI name {:?} contains a tab or newline and cannot be saved", ai.name),
+            ));
+        }
+    }
+    let mut finished = match results_path {
+        Some(path) => load_results(path)?,
+        None => Vec::new(),
+    };
+    let mut file = match results_path {
+        Some(path) => Some(OpenOptions::new().create(true).append(true).open(path)?),
+        None => None,
+    };
+
+    let mut results = Vec::new();
+    for (i, a) in ais.iter().enumerate() {
+        for b in &ais[i + 1..] {
+            let played = finished.iter().position(|result| {
+                result.games() == 2 * seed_number
+                    && ((result.name == a.name && result.opponent == b.name)
+                        || (result.name == b.name && result.opponent == a.name))
+            });
+            if let Some(index) = played {
+                results.push(finished.swap_remove(index));
+                continue;
+            }
+            let result = play_match(a, b, seed_number);
+            on_result(&result);
+            if let Some(file) = file.as_mut() {
+                writeln!(file, "{}", to_line(&result))?;
+                file.flush()?;
+            }
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+#[derive(Clone, Debug)]
+pub struct Rating {
+    pub name: String,
+    pub elo: f64,
+    pub error: f64, // 95%の誤差幅
+    pub games: usize,
+    pub score: f64, // 引き分けを0.5勝として数えた勝率
+    pub time_per_move: Duration,
+}
+
+// 対戦成績からEloレートを最尤推定した順位表
+// BayesEloと同じように、対戦した組み合わせごとにprior_draws局の引き分けを事前に足して
+// 全勝・全敗でもレートが発散しないようにする(そのためprior_drawsは正でなければならない)
+pub struct Leaderboard {
+    pub ratings: Vec<Rating>,
+}
+
+impl Leaderboard {
+    pub fn new(names: &[String], results: &[MatchResult], prior_draws: f64) -> Self {
+        // 0だと勝ち点0のAIのgammaが0になり、幾何平均のln(0)で全員のレートがNaNになる
+        assert!(prior_draws > 0.0, "prior_draws must be positive, got {}", prior_draws);
+        let n = names.len();
+        let index = |name: &str| names.iter().position(|other| other == name);
+        // games[i][j]: iとjの対局数, points[i]: iの勝ち点の合計(いずれも事前の引き分けを含む)
+        let mut games = vec![vec![0.0; n]; n];
+        let mut points = vec![0.0; n];
+        let mut times = vec![Duration::ZERO; n];
+        let mut moves = vec![0; n];
+        let mut real_points = vec![0.0; n];
+        let mut real_games = vec![0; n];
+        for result in results {
+            let (Some(i), Some(j)) = (index(&result.name), index(&result.opponent)) else {
+                continue;
+            };
+            let total = result.games() as f64 + prior_draws;
+            games[i][j] += total;
+            games[j][i] += total;
+            let point = result.wins as f64 + 0.5 * result.draws as f64;
+            points[i] += point + 0.5 * prior_draws;
+            points[j] += total - point - 0.5 * prior_draws;
+            real_points[i] += point;
+            real_points[j] += result.games() as f64 - point;
+            real_games[i] += result.games();
+            real_games[j] += result.games();
+            times[i] += result.times[0];
+            times[j] += result.times[1];
+            moves[i] += result.moves[0];
+            moves[j] += result.moves[1];
+        }
+
+        // Bradley-Terryモデルの強さgammaをMMアルゴリズムで求める
+        let mut gamma = vec![1.0; n];
+        for _ in 0..1000 {
+            let mut next = gamma.clone();
+            for i in 0..n {
+                let denominator: f64 = (0..n)
+                    .filter(|&j| games[i][j] > 0.0)
+                    .map(|j| games[i][j] / (gamma[i] + gamma[j]))
+                    .sum();
+                if denominator > 0.0 {
+                    next[i] = points[i] / denominator;
+                }
+            }
+            // 幾何平均を1にそろえる
+            let log_mean = next.iter().map(|g: &f64| g.ln()).sum::<f64>() / n as f64;
+            next.iter_mut().for_each(|g| *g /= log_mean.exp());
+            let change = gamma.iter().zip(&next).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+            gamma = next;
+            if change < 1e-9 {
+                break;
+            }
+        }
+
+        // 誤差はフィッシャー情報量の対角成分から近似する
+        let elo_scale = 400.0 / 10f64.ln();
+        let mut ratings: Vec<Rating> = (0..n)
+            .map(|i| {
+                let information: f64 = (0..n)
+                    .map(|j| {
+                        let p = gamma[i] / (gamma[i] + gamma[j]);
+                        games[i][j] * p * (1.0 - p)
+                    })
+                    .sum();
+                Rating {
+                    name: names[i].clone(),
+                    elo: elo_scale * gamma[i].ln(),
+                    error: if information > 0.0 {
+                        1.96 * elo_scale / information.sqrt()
+                    } else {
+                        f64::INFINITY
+                    },
+                    games: real_games[i],
+                    score: real_points[i] / real_games[i].max(1) as f64,
+                    time_per_move: if moves[i] > 0 {
+                        times[i] / moves[i] as u32
+                    } else {
+                        Duration::ZERO
+                    },
+                }
+            })
+            .collect();
+        ratings.sort_by(|a, b| b.elo.total_cmp(&a.elo));
+        Self { ratings }
+    }
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "rank\telo\t\tgames\tscore\ttime/move\tname")?;
+        for (rank, rating) in self.ratings.iter().enumerate() {
+            writeln!(
+                f,
+                "{}\t{:+.0} ± {:.0}\t{}\t{:.3}\t{:?}\t{}",
+                rank + 1,
+                rating.elo,
+                rating.error,
+                rating.games,
+                rating.score,
+                rating.time_per_move,
+                rating.name
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use rand::prelude::*;
+use std::fmt;
+
+// 調べるパラメータの範囲
+#[derive(Clone, Debug)]
+pub enum Param {
+    Int { name: &'static str, low: usize, high: usize }, // low以上high以下の整数
+    Float { name: &'static str, low: f64, high: f64, log: bool }, // logなら対数スケールで一様に選ぶ
+}
+
+impl Param {
+    fn name(&self) -> &'static str {
+        match self {
+            Param::Int { name, .. } | Param::Float { name, .. } => name,
+        }
+    }
+
+    fn sample(&self, rng: &mut impl Rng) -> f64 {
+        match *self {
+            Param::Int { low, high, .. } => rng.gen_range(low..=high) as f64,
+            Param::Float { low, high, log: false, .. } => rng.gen_range(low..=high),
+            Param::Float { low, high, log: true, .. } => rng.gen_range(low.ln()..=high.ln()).exp(),
+        }
+    }
+}
+
+// パラメータ空間。宣言した順にConfigへ値が入る
+#[derive(Clone, Debug, Default)]
+pub struct ParamSpace {
+    params: Vec<Param>,
+}
+
+impl ParamSpace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn int(mut self, name: &'static str, low: usize, high: usize) -> Self {
+        assert!(low <= high);
+        self.params.push(Param::Int { name, low, high });
+        self
+    }
+
+    pub fn float(mut self, name: &'static str, low: f64, high: f64) -> Self {
+        assert!(low <= high);
+        self.params.push(Param::Float { name, low, high, log: false });
+        self
+    }
+
+    pub fn log_float(mut self, name: &'static str, low: f64, high: f64) -> Self {
+        assert!(0.0 < low && low <= high);
+        self.params.push(Param::Float { name, low, high, log: true });
+        self
+    }
+
+    pub fn sample(&self, rng: &mut impl Rng) -> Config {
+        Config {
+            values: self.params.iter().map(|param| (param.name(), param.sample(rng))).collect(),
+        }
+    }
+}
+
+// パラメータの名前と値の組
+#[derive(Clone, Debug)]
+pub struct Config {
+    values: Vec<(&'static str, f64)>,
+}
+
+impl Config {
+    pub fn get(&self, name: &str) -> f64 {
+        self.values
+            .iter()
+            .find(|(key, _)| *key == name)
+            .unwrap_or_else(|| panic!("unknown parameter: {}", name))
+            .1
+    }
+
+    pub fn int(&self, name: &str) -> usize {
+        self.get(name) as usize
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            if value.fract() == 0.0 {
+                write!(f, "{}={}", name, value)?;
+            } else {
+                write!(f, "{}={:.3}", name, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// 1つの設定の試行結果。scores[i]はseeds[i]での目的関数の値
+#[derive(Clone, Debug)]
+pub struct Trial {
+    pub config: Config,
+    pub scores: Vec<f64>,
+    pub rounds: usize, // 残ったラウンド数
+}
+
+impl Trial {
+    pub fn mean(&self) -> f64 {
+        self.scores.iter().sum::<f64>() / self.scores.len() as f64
+    }
+}
+
+// 逐次半減法の設定
+// 全設定をmin_seeds個のseedで評価し、上位1/etaだけをeta倍のseedで評価し直すことを繰り返す
+#[derive(Clone, Copy, Debug)]
+pub struct TunerConfig {
+    pub max_evaluations: usize, // 目的関数を呼ぶ回数の上限
+    pub min_seeds: usize,
+    pub eta: usize,
+}
+
+impl Default for TunerConfig {
+    fn default() -> Self {
+        Self {
+            max_evaluations: 200,
+            min_seeds: 2,
+            eta: 2,
+        }
+    }
+}
+
+impl TunerConfig {
+    // roundsラウンド行うときの目的関数を呼ぶ回数。設定の数はeta^rounds
+    // 最初のラウンドは全設定 * min_seeds、それ以降は残った設定が増えたseedの分だけ評価する
+    fn cost(&self, rounds: usize) -> usize {
+        let configs = self.eta.pow(rounds as u32);
+        configs * self.min_seeds + rounds * (configs - configs / self.eta) * self.min_seeds
+    }
+
+    // 予算とseedの数に収まる最大のラウンド数
+    fn rounds(&self, seed_number: usize) -> usize {
+        let mut rounds = 0;
+        while self.cost(rounds + 1) <= self.max_evaluations && self.min_seeds * self.eta.pow(rounds as u32 + 1) <= seed_number {
+            rounds += 1;
+        }
+        rounds
+    }
+}
+
+pub struct TuningResult {
+    pub trials: Vec<Trial>,
+    pub evaluations: usize,
+}
+
+impl TuningResult {
+    // 最後のラウンドまで残った中で平均が最も高い設定
+    pub fn best(&self) -> &Trial {
+        self.trials
+            .iter()
+            .max_by(|a, b| a.rounds.cmp(&b.rounds).then(a.mean().total_cmp(&b.mean())))
+            .unwrap()
+    }
+}
+
+// 全試行を、残ったラウンド数と平均の順に並べて表示する
+impl fmt::Display for TuningResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut order: Vec<&Trial> = self.trials.iter().collect();
+        order.sort_by(|a, b| b.rounds.cmp(&a.rounds).then(b.mean().total_cmp(&a.mean())));
+        writeln!(f, "evaluations: {}", self.evaluations)?;
+        writeln!(f, "rounds\tseeds\tmean\tconfig")?;
+        for trial in order {
+            writeln!(f, "{}\t{}\t{:.2}\t{}", trial.rounds, trial.scores.len(), trial.mean(), trial.config)?;
+        }
+        Ok(())
+    }
+}
+
+// ランダムに選んだ設定を逐次半減法で絞り込む
+// objectiveは設定とseedから値を返し、大きいほど良い
+pub fn tune(
+    space: &ParamSpace,
+    seeds: &[u64],
+    settings: TunerConfig,
+    mut objective: impl FnMut(&Config, u64) -> f64,
+    rng: &mut impl Rng,
+) -> TuningResult {
+    assert!(settings.eta >= 2 && settings.min_seeds >= 1 && seeds.len() >= settings.min_seeds);
+    let rounds = settings.rounds(seeds.len());
+    let mut trials: Vec<Trial> = (0..settings.eta.pow(rounds as u32))
+        .map(|_| Trial {
+            config: space.sample(rng),
+            scores: Vec::new(),
+            rounds: 0,
+        })
+        .collect();
+    let mut survivors: Vec<usize> = (0..trials.len()).collect();
+    let mut evaluations = 0;
+    for round in 0..=rounds {
+        let seed_number = settings.min_seeds * settings.eta.pow(round as u32);
+        for &index in &survivors {
+            let trial = &mut trials[index];
+            for &seed in &seeds[trial.scores.len()..seed_number] {
+                trial.scores.push(objective(&trial.config, seed));
+                evaluations += 1;
+            }
+            trial.rounds = round + 1;
+        }
+        survivors.sort_by(|&a, &b| trials[b].mean().total_cmp(&trials[a].mean()));
+        survivors.truncate((survivors.len() / settings.eta).max(1));
+    }
+    TuningResult { trials, evaluations }
+}
@@ -0,0 +1,31 @@
+// 手番のプレイヤーから見たゲームの結果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WinningStatus {
+    Win,
+    Lose,
+    Draw,
+    Playing,
+}
+
+// 二人交互ゲームの状態
+// 評価や勝敗は全て手番のプレイヤーから見た値で返す
+pub trait TwoPlayerState: Clone {
+    fn legal_actions(&self) -> Vec<usize>;
+    fn advance(&mut self, action: usize);
+    fn is_done(&self) -> bool;
+    fn winning_status(&self) -> WinningStatus;
+    // 手番のプレイヤーから見た評価
+    fn get_score(&self) -> isize;
+    // 置換表のキー。同じ局面なら同じ値を返す
+    fn hash(&self) -> u64;
+}
+
+// 二人同時手番ゲームの状態
+// プレイヤーは0と1で表し、勝敗は指定したプレイヤーから見た値で返す
+pub trait SimultaneousState: Clone {
+    fn legal_actions(&self, player: usize) -> Vec<usize>;
+    // 二人の行動を同時に適用する
+    fn advance(&mut self, actions: [usize; 2]);
+    fn is_done(&self) -> bool;
+    fn winning_status(&self, player: usize) -> WinningStatus;
+}